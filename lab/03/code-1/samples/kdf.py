@@ -0,0 +1,70 @@
+import subprocess
+
+# PBKDF2-HMAC-SHA256 vectors for the `derive` command: the RFC 6070 inputs with
+# SHA-256 as the PRF, and the PBKDF2 vectors from RFC 7914 section 11.
+#
+# Run from the crate root with:
+#     python3 samples/kdf.py
+vectors = [
+    (b"password", b"salt", 1, 32,
+     "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"),
+    (b"password", b"salt", 2, 32,
+     "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"),
+    (b"password", b"salt", 4096, 32,
+     "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"),
+    (b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, 40,
+     "348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9"),
+    (b"passwd", b"salt", 1, 64,
+     "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc"
+     "49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"),
+    (b"Password", b"NaCl", 80000, 64,
+     "4ddcd8f60b98be21830cee5ef22701f9641a4418d04c0414aeff08876b34ab56"
+     "a1d425a1225833549adb841b51c9b3176a272bdebba1d078478f62b397f33c8d"),
+]
+
+subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+
+for passphrase, salt, iterations, length, expected in vectors:
+    output = subprocess.run([
+        "target/release/code-1", "derive",
+        "--passphrase", passphrase.decode(),
+        "--salt", salt.hex(),
+        "--iterations", str(iterations),
+        "--length", str(length),
+    ], capture_output=True, check=True, text=True).stdout.strip()
+    assert output == expected, f"{passphrase!r}/{salt!r}/{iterations}: got {output}"
+
+# RFC 8018 requires at least one iteration, on the command line and in a header.
+output = subprocess.run([
+    "target/release/code-1", "derive", "--passphrase", "password", "--salt", "00", "--iterations", "0",
+], capture_output=True)
+assert output.returncode != 0 and output.stdout == b""
+header = b"PBE1" + (0).to_bytes(4, "little") + bytes(16)
+output = subprocess.run(
+    ["target/release/code-1", "decrypt", "--passphrase", "password"], input=header + bytes(16), capture_output=True,
+)
+assert output.returncode != 0 and b"invalid passphrase header" in output.stderr
+
+# The output length must be between 1 and 1024 bytes.
+for length in ["0", "1025", "4294967295"]:
+    output = subprocess.run([
+        "target/release/code-1", "derive", "--passphrase", "password", "--salt", "00", "--length", length,
+    ], capture_output=True)
+    assert output.returncode != 0 and output.stdout == b"", f"--length {length} accepted"
+
+# Decryption reads the iteration count and salt from the header and refuses to be given either.
+ciphertext = subprocess.run(
+    ["target/release/code-1", "encrypt", "--passphrase", "password", "--iterations", "1000"],
+    input=b"attack at dawn", capture_output=True, check=True,
+).stdout
+for flag, value in [("--iterations", "1000"), ("--salt", ciphertext[8:24].hex())]:
+    output = subprocess.run(
+        ["target/release/code-1", "decrypt", "--passphrase", "password", flag, value], input=ciphertext, capture_output=True,
+    )
+    assert output.returncode != 0 and output.stdout == b"", f"{flag} accepted when decrypting"
+output = subprocess.run(
+    ["target/release/code-1", "decrypt", "--passphrase", "password"], input=ciphertext, capture_output=True, check=True,
+)
+assert output.stdout == b"attack at dawn"
+
+print(f"{len(vectors)} vectors OK")
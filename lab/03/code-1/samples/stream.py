@@ -0,0 +1,98 @@
+import os
+import random
+import subprocess
+import sys
+import tempfile
+
+# Round trips for the streaming `encrypt`/`decrypt` commands. Inputs that are
+# empty or sit on either side of a CHUNK_SIZE read boundary go through every
+# mode and key size, from stdin and from --input/--output files, and each
+# ciphertext must match `openssl enc` byte for byte. Decryption must fail on
+# bad PKCS#7 padding and on ciphertexts that are not a whole number of blocks.
+#
+# Run from the crate root with:
+#     python3 samples/stream.py [rounds]
+CHUNK_SIZE = 1 << 16  # src/main.rs
+BINARY = "target/release/code-1"
+MODES = ["cbc", "ecb", "ctr", "cfb", "ofb"]
+
+subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+
+
+def code1(command, key, iv, mode, data):
+    args = [BINARY, command, "--key", key.hex(), "--mode", mode]
+    if mode != "ecb":
+        args += ["--iv", iv.hex()]
+    return subprocess.run(args, input=data, capture_output=True)
+
+
+def openssl(key, iv, mode, data):
+    args = ["openssl", "enc", f"-aes-{len(key) * 8}-{mode}", "-K", key.hex()]
+    if mode != "ecb":
+        args += ["-iv", iv.hex()]
+    return subprocess.run(args, input=data, capture_output=True, check=True).stdout
+
+
+def files(tmp, command, key, iv, mode, data):
+    # Same as `code1`, but through --input and --output instead of the pipes.
+    src, dst = os.path.join(tmp, "in"), os.path.join(tmp, "out")
+    with open(src, "wb") as f:
+        f.write(data)
+    args = [BINARY, command, "--key", key.hex(), "--mode", mode, "--input", src, "--output", dst]
+    if mode != "ecb":
+        args += ["--iv", iv.hex()]
+    subprocess.run(args, capture_output=True, check=True)
+    with open(dst, "rb") as f:
+        return f.read()
+
+
+rng = random.Random(2025)
+rounds = int(sys.argv[1]) if len(sys.argv) > 1 else 1
+sizes = [0, 1, 15, 16, 17, CHUNK_SIZE - 1, CHUNK_SIZE, CHUNK_SIZE + 1, 2 * CHUNK_SIZE + 16]
+
+with tempfile.TemporaryDirectory() as tmp:
+    for _ in range(rounds):
+        for size in sizes:
+            for mode in MODES:
+                key = rng.randbytes(rng.choice([16, 24, 32]))
+                iv = rng.randbytes(16)
+                text = rng.randbytes(size)
+
+                out = code1("encrypt", key, iv, mode, text)
+                assert out.returncode == 0, out.stderr
+                ciphertext = out.stdout
+                assert ciphertext == openssl(key, iv, mode, text), f"{mode}, {size} bytes"
+                assert files(tmp, "encrypt", key, iv, mode, text) == ciphertext
+
+                out = code1("decrypt", key, iv, mode, ciphertext)
+                assert out.returncode == 0 and out.stdout == text, f"{mode}, {size} bytes"
+                assert files(tmp, "decrypt", key, iv, mode, ciphertext) == text
+        print(f"{len(sizes)} sizes x {len(MODES)} modes OK")
+
+    # openssl -nopad encrypts the final blocks as given, so the padding is whatever we put there.
+    key, iv = rng.randbytes(16), rng.randbytes(16)
+    body = rng.randbytes(CHUNK_SIZE)
+    bad_padding = {
+        "padding length 0": bytes(15) + b"\x00",
+        "padding length 17": bytes(15) + b"\x11",
+        "inconsistent padding": bytes(13) + b"\x03\x02\x03",
+    }
+    for mode in ["cbc", "ecb"]:
+        for name, last in bad_padding.items():
+            ciphertext = subprocess.run(
+                ["openssl", "enc", f"-aes-128-{mode}", "-nopad", "-K", key.hex()]
+                + (["-iv", iv.hex()] if mode != "ecb" else []),
+                input=body + last, capture_output=True, check=True,
+            ).stdout
+            out = code1("decrypt", key, iv, mode, ciphertext)
+            assert out.returncode != 0, f"{mode}: {name} accepted"
+
+        ciphertext = openssl(key, iv, mode, body)
+        for name, truncated in {
+            "empty": b"",
+            "one byte short": ciphertext[:-1],
+            "one byte past a chunk": ciphertext[:CHUNK_SIZE + 1],
+        }.items():
+            out = code1("decrypt", key, iv, mode, truncated)
+            assert out.returncode != 0, f"{mode}: {name} ciphertext accepted"
+    print("bad padding and truncated input rejected")
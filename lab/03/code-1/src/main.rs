@@ -1,6 +1,12 @@
+use std::env;
+use std::fmt;
 use std::fs;
 use std::io;
+use std::process;
 
+use rand::{TryRngCore, rngs::OsRng};
+
+#[cfg(not(feature = "constant_time"))]
 const SBOX: [u8; 256] = [
     0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
     0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
@@ -20,6 +26,7 @@ const SBOX: [u8; 256] = [
     0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
 ];
 
+#[cfg(not(feature = "constant_time"))]
 const INV_SBOX: [u8; 256] = [
     0x52, 0x09, 0x6a, 0xd5, 0x30, 0x36, 0xa5, 0x38, 0xbf, 0x40, 0xa3, 0x9e, 0x81, 0xf3, 0xd7, 0xfb,
     0x7c, 0xe3, 0x39, 0x82, 0x9b, 0x2f, 0xff, 0x87, 0x34, 0x8e, 0x43, 0x44, 0xc4, 0xde, 0xe9, 0xcb,
@@ -44,98 +51,724 @@ const RCON: [u8; 11] = [
 ];
 
 fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let result = if args.is_empty() { run() } else { run_cli(&args) };
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Error> {
     let mut reader: Box<dyn io::Read> = if cfg!(feature = "online_judge") {
         Box::new(io::stdin())
     } else {
-        Box::new(fs::File::open("samples/input.bin").unwrap())
+        Box::new(fs::File::open("samples/input.bin")?)
     };
     let mut writer: Box<dyn io::Write> = if cfg!(feature = "online_judge") {
         Box::new(io::stdout())
     } else {
-        Box::new(fs::File::create("samples/output.bin").unwrap())
+        Box::new(fs::File::create("samples/output.bin")?)
     };
 
     let mode = {
         let mut buf = [0u8; 1];
-        reader.read_exact(&mut buf).unwrap();
+        reader.read_exact(&mut buf)?;
         buf[0]
     };
 
+    // Bits 4-5 of the mode byte select the key size, which in turn fixes the round count.
+    let key_len = match mode & 0x30 {
+        0x00 => 16,
+        0x10 => 24,
+        0x20 => 32,
+        _ => return Err(Error::InvalidMode(mode)),
+    };
+
+    // XTS takes two keys of the selected size: the data key followed by the tweak key.
     let key = {
-        let mut buf = [0u8; 16];
-        reader.read_exact(&mut buf).unwrap();
+        let mut buf = vec![0u8; if mode & 0x0f == 0x0a { 2 * key_len } else { key_len }];
+        reader.read_exact(&mut buf)?;
         buf
     };
-    let words = key_expansion(&key).chunks(4)
-        .map(|chunk| {
-            let mut round_key = [0u8; 16];
-            for i in 0..4 {
-                round_key[(i * 4)..(i * 4 + 4)].copy_from_slice(&chunk[i].to_be_bytes());
+
+    if mode & 0x0f == 0x0a {
+        // IEEE 1619 only defines XTS-AES-128 and XTS-AES-256.
+        if key_len == 24 {
+            return Err(Error::InvalidMode(mode));
+        }
+        let (words, tweak_words) = (round_keys(&key[..key_len]), round_keys(&key[key_len..]));
+
+        let tweak = {
+            let mut buf = [0u8; 16];
+            reader.read_exact(&mut buf)?;
+            buf
+        };
+
+        let text = {
+            let mut buf = [0u8; 4];
+            reader.read_exact(&mut buf)?;
+            let mut buf = vec![0u8; u32::from_le_bytes(buf) as usize];
+            reader.read_exact(&mut buf)?;
+            buf
+        };
+
+        let result = if mode & 0x80 == 0 {
+            xts::encrypt_unit(&words, &tweak_words, &tweak, &text)?
+        } else {
+            xts::decrypt_unit(&words, &tweak_words, &tweak, &text)?
+        };
+        writer.write_all(&result)?;
+        return Ok(());
+    }
+
+    let words = round_keys(&key);
+
+    if mode & 0x0f == 0x09 {
+        let text = {
+            let mut buf = [0u8; 4];
+            reader.read_exact(&mut buf)?;
+            let mut buf = vec![0u8; u32::from_le_bytes(buf) as usize];
+            reader.read_exact(&mut buf)?;
+            buf
+        };
+
+        // The decrypt bit turns tag generation into verification of a trailing tag.
+        if mode & 0x80 == 0 {
+            writer.write_all(&cmac::mac(&words, &text))?;
+        } else {
+            let tag = {
+                let mut buf = [0u8; 16];
+                reader.read_exact(&mut buf)?;
+                buf
+            };
+            if !cmac::verify(&words, &text, &tag) {
+                return Err(Error::AuthenticationFailed);
             }
-            transpose(&round_key)
-        })
-        .collect::<Vec<[u8; 16]>>();
+        }
+        return Ok(());
+    }
+
+    if mode & 0x0f == 0x06 {
+        let nonce = {
+            let mut buf = [0u8; 12];
+            reader.read_exact(&mut buf)?;
+            buf
+        };
+
+        let aad = {
+            let mut buf = [0u8; 4];
+            reader.read_exact(&mut buf)?;
+            let mut buf = vec![0u8; u32::from_le_bytes(buf) as usize];
+            reader.read_exact(&mut buf)?;
+            buf
+        };
+
+        let text = {
+            let mut buf = [0u8; 4];
+            reader.read_exact(&mut buf)?;
+            let mut buf = vec![0u8; u32::from_le_bytes(buf) as usize];
+            reader.read_exact(&mut buf)?;
+            buf
+        };
+
+        if mode & 0x80 == 0 {
+            let (result, tag) = gcm::seal(&words, &nonce, &aad, &text);
+            writer.write_all(&result)?;
+            writer.write_all(&tag)?;
+        } else {
+            let tag = {
+                let mut buf = [0u8; 16];
+                reader.read_exact(&mut buf)?;
+                buf
+            };
+            // Nothing is written unless the tag verifies.
+            match gcm::open(&words, &nonce, &aad, &text, &tag) {
+                Some(result) => writer.write_all(&result)?,
+                None => return Err(Error::AuthenticationFailed),
+            }
+        }
+        return Ok(());
+    }
+
+    if mode & 0x0f == 0x07 || mode & 0x0f == 0x08 {
+        let text = {
+            let mut buf = [0u8; 4];
+            reader.read_exact(&mut buf)?;
+            let mut buf = vec![0u8; u32::from_le_bytes(buf) as usize];
+            reader.read_exact(&mut buf)?;
+            buf
+        };
 
-    let mut iv = {
+        let result = match mode & 0x8f {
+            0x07 => keywrap::wrap(&words, &text)?,
+            0x87 => keywrap::unwrap(&words, &text)?,
+            0x08 => keywrap::wrap_pad(&words, &text)?,
+            0x88 => keywrap::unwrap_pad(&words, &text)?,
+            _ => return Err(Error::InvalidMode(mode)),
+        };
+        writer.write_all(&result)?;
+        return Ok(());
+    }
+
+    let mut iv = if mode & 0x0f == 0x02 {
+        // ECB has no chaining value.
+        [0u8; 16]
+    } else {
         let mut buf = [0u8; 16];
-        reader.read_exact(&mut buf).unwrap();
-        transpose(&buf)
+        reader.read_exact(&mut buf)?;
+        buf
     };
 
     let n = {
         let mut buf = [0u8; 4];
-        reader.read_exact(&mut buf).unwrap();
+        reader.read_exact(&mut buf)?;
         u32::from_le_bytes(buf) as usize
     };
 
-    match mode {
-        0x01 => {
-            for m in (0..=n).step_by(16) {
-                let text = if m + 16 <= n {
-                    let mut buf = [0u8; 16];
-                    reader.read_exact(&mut buf).unwrap();
-                    buf
-                } else {
-                    let mut buf = vec![0u8; n - m];
-                    reader.read_exact(&mut buf).unwrap();
-                    let padding_len = 16 - (n - m);
-                    buf.extend(vec![padding_len as u8; padding_len]);
-                    buf.try_into().unwrap()
-                };
+    let (padding, process) = block_mode(mode).ok_or(Error::InvalidMode(mode))?;
+
+    if padding == Padding::Strip && (n == 0 || n % 16 != 0) {
+        return Err(Error::CiphertextLength(n));
+    }
+
+    stream(&mut Exact { inner: reader, remaining: n }, &mut writer, &words, &mut iv, process, padding)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+const USAGE: &str = "\
+usage: code-1 <encrypt|decrypt> (--key <hex> | --key-file <path> | --passphrase <text>)
+              [--iv <hex>] [--mode <cbc|ecb|ctr|cfb|ofb>] [--input <path>] [--output <path>]
+              [--iterations <n>] [--salt <hex>]
+       code-1 derive --passphrase <text> --salt <hex> [--iterations <n>] [--length <n>]
+       code-1 <wrap|unwrap> (--key <hex> | --key-file <path>) [--padded]
+              [--input <path>] [--output <path>]
+
+The key size (16, 24 or 32 bytes) selects AES-128, AES-192 or AES-256. Every mode
+except ECB needs a 16-byte --iv. Input and output default to stdin and stdout.
+
+With --passphrase, an AES-256 key and the IV are derived with PBKDF2-HMAC-SHA256 and
+the salt and iteration count are stored in a 24-byte header ahead of the ciphertext;
+the salt is random unless --salt gives exactly 16 bytes. `derive` prints the raw PBKDF2
+output in hex and takes a salt of any length, so published test vectors can be reproduced.
+
+`wrap` and `unwrap` protect key data under the given key-encryption key with AES Key
+Wrap (RFC 3394), or with AES Key Wrap with Padding (RFC 5649) when --padded is set.";
 
-                assert_eq!(text.len(), 16);
+/// Magic bytes opening a passphrase-encrypted file, followed by the iteration count and salt.
+const PASSPHRASE_MAGIC: [u8; 4] = *b"PBE1";
+const DEFAULT_ITERATIONS: u32 = 600_000;
+/// Upper bound on the iteration count, so a crafted header cannot demand billions of rounds.
+const MAX_ITERATIONS: u32 = 10_000_000;
+/// Upper bound on the `derive` output length in bytes.
+const MAX_DERIVED_LENGTH: usize = 1024;
 
-                let result = encrypt(&words, &mut iv, &text);
+/// Streams a file through the cipher without the length-prefixed judge format.
+fn run_cli(args: &[String]) -> Result<(), Error> {
+    let command = args[0].as_str();
+    if command == "-h" || command == "--help" {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    if !["encrypt", "decrypt", "derive", "wrap", "unwrap"].contains(&command) {
+        return Err(Error::Usage(format!("unknown command `{}`", command)));
+    }
+    let decrypt = command == "decrypt";
+
+    let mut key = None;
+    let mut iv = None;
+    let mut mode = 0x01;
+    let mut input = None;
+    let mut output = None;
+    let mut passphrase = None;
+    let mut iterations = None;
+    let mut salt = None;
+    let mut length = 32;
+    let mut padded = false;
 
-                writer.write_all(&result).unwrap();
+    let mut iter = args[1..].iter();
+    while let Some(flag) = iter.next() {
+        let mut value = || iter.next().ok_or_else(|| Error::Usage(format!("missing value for `{}`", flag)));
+        match flag.as_str() {
+            "-k" | "--key" => key = Some(parse_hex(value()?)?),
+            "--key-file" => key = Some(fs::read(value()?)?),
+            "--iv" => iv = Some(parse_hex(value()?)?),
+            "-m" | "--mode" => {
+                mode = match value()?.as_str() {
+                    "cbc" => 0x01,
+                    "ecb" => 0x02,
+                    "ctr" => 0x03,
+                    "cfb" => 0x04,
+                    "ofb" => 0x05,
+                    other => return Err(Error::Usage(format!("unknown mode `{}`", other))),
+                }
             }
-        },
-        0x81 => {
-            for m in (0..n).step_by(16) {
-                let text = {
-                    let mut buf = [0u8; 16];
-                    reader.read_exact(&mut buf).unwrap();
-                    buf
-                };
+            "-i" | "--input" => input = Some(value()?.clone()),
+            "-o" | "--output" => output = Some(value()?.clone()),
+            "-p" | "--passphrase" => passphrase = Some(value()?.clone()),
+            "--iterations" => iterations = Some(parse_iterations(value()?)?),
+            "--salt" => salt = Some(parse_hex(value()?)?),
+            "--length" => length = parse_length(value()?)?,
+            "--padded" => padded = true,
+            other => return Err(Error::Usage(format!("unknown option `{}`", other))),
+        }
+    }
+
+    if command == "derive" {
+        let passphrase = passphrase.ok_or_else(|| Error::Usage("missing --passphrase".to_string()))?;
+        // Unlike the 16-byte salt of the PBE1 header, any length is allowed here: the RFC 6070
+        // and RFC 7914 vectors use 4- and 36-byte salts, and nothing is stored alongside.
+        let salt = salt.ok_or_else(|| Error::Usage("missing --salt".to_string()))?;
+        let iterations = iterations.unwrap_or(DEFAULT_ITERATIONS);
+        let derived = pbkdf2::pbkdf2_hmac_sha256(passphrase.as_bytes(), &salt, iterations, length);
+        println!("{}", derived.iter().map(|b| format!("{:02x}", b)).collect::<String>());
+        return Ok(());
+    }
 
-                assert_eq!(text.len(), 16);
+    let mut reader: Box<dyn io::Read> = match input {
+        Some(path) => Box::new(fs::File::open(path)?),
+        None => Box::new(io::stdin().lock()),
+    };
+    let mut writer: Box<dyn io::Write> = match output {
+        Some(path) => Box::new(fs::File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    };
 
-                let result = transpose(&decrypt(&words, &mut iv, &text));
+    if command == "wrap" || command == "unwrap" {
+        let words = round_keys(&check_key(key)?);
+        let mut text = Vec::new();
+        reader.read_to_end(&mut text)?;
 
-                if m + 16 == n {
-                    let padding_len = result[15] as usize;
-                    if padding_len < 16 {
-                        writer.write_all(&result[..16 - padding_len]).unwrap();
-                    }
-                    break;
+        let result = match (command, padded) {
+            ("wrap", false) => keywrap::wrap(&words, &text)?,
+            ("wrap", true) => keywrap::wrap_pad(&words, &text)?,
+            (_, false) => keywrap::unwrap(&words, &text)?,
+            (_, true) => keywrap::unwrap_pad(&words, &text)?,
+        };
+        writer.write_all(&result)?;
+        writer.flush()?;
+        return Ok(());
+    }
+
+    if let Some(passphrase) = passphrase {
+        if key.is_some() || iv.is_some() {
+            return Err(Error::Usage("--passphrase cannot be combined with --key or --iv".to_string()));
+        }
+
+        let (iterations, salt) = if decrypt {
+            // Both come from the header; a value given here would be silently ignored.
+            if iterations.is_some() || salt.is_some() {
+                return Err(Error::Usage("--iterations and --salt are read from the header when decrypting".to_string()));
+            }
+            let mut header = [0u8; 24];
+            reader.read_exact(&mut header)?;
+            let iterations = u32::from_le_bytes(header[4..8].try_into().unwrap());
+            if header[..4] != PASSPHRASE_MAGIC || !(1..=MAX_ITERATIONS).contains(&iterations) {
+                return Err(Error::BadHeader);
+            }
+            (iterations, header[8..].to_vec())
+        } else {
+            let iterations = iterations.unwrap_or(DEFAULT_ITERATIONS);
+            let salt = match salt {
+                Some(salt) if salt.len() == 16 => salt,
+                Some(_) => return Err(Error::Usage("salt must be 16 bytes".to_string())),
+                None => {
+                    let mut buf = vec![0u8; 16];
+                    OsRng.try_fill_bytes(&mut buf).unwrap();
+                    buf
                 }
+            };
+            writer.write_all(&PASSPHRASE_MAGIC)?;
+            writer.write_all(&iterations.to_le_bytes())?;
+            writer.write_all(&salt)?;
+            (iterations, salt)
+        };
+
+        let derived = pbkdf2::pbkdf2_hmac_sha256(passphrase.as_bytes(), &salt, iterations, 48);
+        key = Some(derived[..32].to_vec());
+        iv = Some(derived[32..].to_vec());
+    }
+
+    let words = round_keys(&check_key(key)?);
+
+    let mut iv: [u8; 16] = match iv {
+        Some(iv) => iv.try_into().map_err(|_| Error::Usage("IV must be 16 bytes".to_string()))?,
+        None if mode == 0x02 => [0u8; 16],
+        None => return Err(Error::Usage("missing --iv".to_string())),
+    };
+
+    let (padding, process) = block_mode(mode | if decrypt { 0x80 } else { 0x00 }).unwrap();
+    stream(&mut reader, &mut writer, &words, &mut iv, process, padding)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn check_key(key: Option<Vec<u8>>) -> Result<Vec<u8>, Error> {
+    let key = key.ok_or_else(|| Error::Usage("missing --key, --key-file or --passphrase".to_string()))?;
+    if ![16, 24, 32].contains(&key.len()) {
+        return Err(Error::Usage(format!("key must be 16, 24 or 32 bytes, got {}", key.len())));
+    }
+    Ok(key)
+}
+
+fn parse_number(text: &str) -> Result<u32, Error> {
+    text.parse().map_err(|_| Error::Usage(format!("invalid number `{}`", text)))
+}
+
+/// PBKDF2 needs at least one iteration (RFC 8018 section 5.2).
+fn parse_iterations(text: &str) -> Result<u32, Error> {
+    match parse_number(text)? {
+        n @ 1..=MAX_ITERATIONS => Ok(n),
+        n => Err(Error::Usage(format!("iteration count must be between 1 and {}, got {}", MAX_ITERATIONS, n))),
+    }
+}
+
+fn parse_length(text: &str) -> Result<usize, Error> {
+    match parse_number(text)? as usize {
+        n @ 1..=MAX_DERIVED_LENGTH => Ok(n),
+        n => Err(Error::Usage(format!("length must be between 1 and {}, got {}", MAX_DERIVED_LENGTH, n))),
+    }
+}
+
+fn parse_hex(text: &str) -> Result<Vec<u8>, Error> {
+    let invalid = || Error::Usage(format!("invalid hex string `{}`", text));
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).map_err(|_| invalid()))
+        .collect()
+}
+
+#[derive(Debug)]
+enum Error {
+    /// The input ended before the declared number of bytes was read.
+    ShortRead,
+    /// The ciphertext of a padded mode is empty or not a multiple of 16 bytes.
+    CiphertextLength(usize),
+    /// The final PKCS#7 byte is not in `1..=16`.
+    PaddingLength(u8),
+    /// The padding bytes do not all equal the padding length.
+    InconsistentPadding,
+    /// The GCM or CMAC tag did not verify.
+    AuthenticationFailed,
+    /// The key data has a length the key wrap variant cannot handle.
+    KeyDataLength(usize),
+    /// The integrity check value of unwrapped key data did not verify.
+    IntegrityCheck,
+    /// An XTS data unit is shorter than one block.
+    DataUnitLength(usize),
+    InvalidMode(u8),
+    /// The input does not start with the passphrase header, or its iteration count is out of range.
+    BadHeader,
+    Usage(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ShortRead => write!(f, "input is truncated"),
+            Error::CiphertextLength(n) => write!(f, "ciphertext length {} is not a positive multiple of 16", n),
+            Error::PaddingLength(len) => write!(f, "invalid padding length {}", len),
+            Error::InconsistentPadding => write!(f, "inconsistent padding bytes"),
+            Error::AuthenticationFailed => write!(f, "authentication failed"),
+            Error::KeyDataLength(n) => write!(f, "invalid key data length {}", n),
+            Error::IntegrityCheck => write!(f, "integrity check failed"),
+            Error::DataUnitLength(n) => write!(f, "XTS data unit length {} is shorter than 16", n),
+            Error::InvalidMode(mode) => write!(f, "invalid mode 0x{:02x}", mode),
+            Error::BadHeader => write!(f, "missing or invalid passphrase header"),
+            Error::Usage(msg) => write!(f, "{}\n\n{}", msg, USAGE),
+            Error::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
 
-                writer.write_all(&result).unwrap();
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            Error::ShortRead
+        } else {
+            Error::Io(err)
+        }
+    }
+}
+
+/// Strips PKCS#7 padding from the final plaintext block.
+fn unpad(block: &[u8; 16]) -> Result<&[u8], Error> {
+    let padding_len = block[15];
+    if padding_len == 0 || padding_len > 16 {
+        return Err(Error::PaddingLength(padding_len));
+    }
+
+    let (text, padding) = block.split_at(16 - padding_len as usize);
+    if padding.iter().any(|&b| b != padding_len) {
+        return Err(Error::InconsistentPadding);
+    }
+    Ok(text)
+}
+
+/// Size of each read, so memory use stays bounded regardless of the input length.
+const CHUNK_SIZE: usize = 1 << 16;
 
-                iv = transpose(&text);
+#[derive(Clone, Copy, PartialEq)]
+enum Padding {
+    /// Stream modes: the final partial block is truncated after processing.
+    None,
+    /// Block mode encryption: a PKCS#7 padding block is always appended.
+    Add,
+    /// Block mode decryption: the final block is held back and unpadded.
+    Strip,
+}
+
+/// Maps the chaining-mode and direction bits of a mode byte to its block function.
+fn block_mode(mode: u8) -> Option<(Padding, BlockMode)> {
+    Some(match mode & 0x8f {
+        0x01 => (Padding::Add, cbc_encrypt as BlockMode),
+        0x81 => (Padding::Strip, cbc_decrypt),
+        0x02 => (Padding::Add, ecb_encrypt),
+        0x82 => (Padding::Strip, ecb_decrypt),
+        0x03 | 0x83 => (Padding::None, ctr_apply),
+        0x04 => (Padding::None, cfb_encrypt),
+        0x84 => (Padding::None, cfb_decrypt),
+        0x05 | 0x85 => (Padding::None, ofb_apply),
+        _ => return None,
+    })
+}
+
+/// Runs everything `reader` yields through `process` in `CHUNK_SIZE` pieces.
+fn stream(
+    reader: &mut dyn io::Read,
+    writer: &mut dyn io::Write,
+    words: &[[u8; 16]],
+    iv: &mut [u8; 16],
+    process: BlockMode,
+    padding: Padding,
+) -> Result<(), Error> {
+    let mut buf = vec![0u8; CHUNK_SIZE + 16];
+    let mut result = Vec::with_capacity(CHUNK_SIZE + 16);
+    let mut pending = 0;
+    let mut total = 0;
+
+    loop {
+        let read = match reader.read(&mut buf[pending..]) {
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        };
+        pending += read;
+        total += read;
+
+        // While decrypting, the last complete block may be the padding block.
+        let mut ready = pending / 16 * 16;
+        if padding == Padding::Strip && ready == pending && ready > 0 {
+            ready -= 16;
+        }
+
+        result.clear();
+        buf[..ready].chunks_exact(16).for_each(|chunk| {
+            result.extend_from_slice(&process(words, iv, chunk.try_into().unwrap()));
+        });
+        writer.write_all(&result)?;
+
+        buf.copy_within(ready..pending, 0);
+        pending -= ready;
+
+        if read == 0 {
+            break;
+        }
+    }
+
+    let mut text = [0u8; 16];
+    text[..pending].copy_from_slice(&buf[..pending]);
+    match padding {
+        Padding::None => {
+            if pending > 0 {
+                writer.write_all(&process(words, iv, &text)[..pending])?;
+            }
+        }
+        Padding::Add => {
+            text[pending..].fill((16 - pending) as u8);
+            writer.write_all(&process(words, iv, &text))?;
+        }
+        Padding::Strip => {
+            if pending != 16 {
+                return Err(Error::CiphertextLength(total));
             }
-        },
-        _ => panic!("Invalid mode."),
+            writer.write_all(unpad(&process(words, iv, &text))?)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Yields exactly `remaining` bytes of `inner`, reporting an early end of input as an error.
+struct Exact<R> {
+    inner: R,
+    remaining: usize,
+}
+
+impl<R: io::Read> io::Read for Exact<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.remaining == 0 {
+            return Ok(0);
+        }
+        let len = buf.len().min(self.remaining);
+        let read = self.inner.read(&mut buf[..len])?;
+        if read == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        self.remaining -= read;
+        Ok(read)
+    }
+}
+
+/// Processes one 16-byte block under a chaining mode, updating the chaining value `iv`.
+type BlockMode = fn(&[[u8; 16]], &mut [u8; 16], &[u8; 16]) -> [u8; 16];
+
+fn ecb_encrypt(words: &[[u8; 16]], _iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
+    encrypt(words, text)
+}
+fn ecb_decrypt(words: &[[u8; 16]], _iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
+    decrypt(words, text)
+}
+
+fn cbc_encrypt(words: &[[u8; 16]], iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
+    *iv = encrypt(words, &xor_block(text, iv));
+    *iv
+}
+fn cbc_decrypt(words: &[[u8; 16]], iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
+    let result = xor_block(&decrypt(words, text), iv);
+    *iv = *text;
+    result
+}
+
+/// The counter block is incremented as a single 128-bit big-endian integer.
+fn ctr_apply(words: &[[u8; 16]], iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
+    let result = xor_block(text, &encrypt(words, iv));
+    *iv = (u128::from_be_bytes(*iv).wrapping_add(1)).to_be_bytes();
+    result
+}
+
+/// CFB-128: the feedback is always the full ciphertext block.
+fn cfb_encrypt(words: &[[u8; 16]], iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
+    *iv = xor_block(text, &encrypt(words, iv));
+    *iv
+}
+fn cfb_decrypt(words: &[[u8; 16]], iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
+    let result = xor_block(text, &encrypt(words, iv));
+    *iv = *text;
+    result
+}
+
+fn ofb_apply(words: &[[u8; 16]], iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
+    *iv = encrypt(words, iv);
+    xor_block(text, iv)
+}
+
+fn xor_block(a: &[u8; 16], b: &[u8; 16]) -> [u8; 16] {
+    let mut res = [0u8; 16];
+    for i in 0..16 {
+        res[i] = a[i] ^ b[i];
+    }
+    res
+}
+
+/// Multiplies by x in GF(2^128) modulo x^128 + x^7 + x^2 + x + 1. CMAC loads blocks big-endian
+/// and XTS little-endian, after which both reduce to the same shift and conditional 0x87.
+fn gf_double(x: u128) -> u128 {
+    (x << 1) ^ (0x87 & (x >> 127).wrapping_neg())
+}
+
+/// Galois/Counter Mode (NIST SP 800-38D) with 96-bit nonces and 128-bit tags.
+mod gcm {
+    use crate::encrypt;
+
+    /// Encrypts `text` and authenticates it together with `aad`, returning the ciphertext and tag.
+    pub fn seal(words: &[[u8; 16]], nonce: &[u8; 12], aad: &[u8], text: &[u8]) -> (Vec<u8>, [u8; 16]) {
+        let h = u128::from_be_bytes(encrypt(words, &[0u8; 16]));
+        let j0 = initial_counter(nonce);
+
+        let result = ctr(words, &j0, text);
+        let tag = compute_tag(words, h, &j0, aad, &result);
+        (result, tag)
+    }
+
+    /// Verifies `tag` over `aad` and `text`, and only then decrypts `text`.
+    pub fn open(words: &[[u8; 16]], nonce: &[u8; 12], aad: &[u8], text: &[u8], tag: &[u8; 16]) -> Option<Vec<u8>> {
+        let h = u128::from_be_bytes(encrypt(words, &[0u8; 16]));
+        let j0 = initial_counter(nonce);
+
+        let expected = compute_tag(words, h, &j0, aad, text);
+        let diff = expected.iter().zip(tag.iter()).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        if diff != 0 {
+            return None;
+        }
+        Some(ctr(words, &j0, text))
+    }
+
+    fn initial_counter(nonce: &[u8; 12]) -> [u8; 16] {
+        let mut j0 = [0u8; 16];
+        j0[..12].copy_from_slice(nonce);
+        j0[15] = 1;
+        j0
+    }
+
+    /// Increments the rightmost 32 bits of the counter block, wrapping modulo 2^32.
+    fn inc32(block: &[u8; 16]) -> [u8; 16] {
+        let mut res = *block;
+        let counter = u32::from_be_bytes(block[12..16].try_into().unwrap()).wrapping_add(1);
+        res[12..16].copy_from_slice(&counter.to_be_bytes());
+        res
+    }
+
+    fn ctr(words: &[[u8; 16]], j0: &[u8; 16], text: &[u8]) -> Vec<u8> {
+        let mut counter = *j0;
+        text.chunks(16)
+            .flat_map(|chunk| {
+                counter = inc32(&counter);
+                let keystream = encrypt(words, &counter);
+                chunk.iter().zip(keystream).map(|(a, b)| a ^ b).collect::<Vec<u8>>()
+            })
+            .collect()
+    }
+
+    fn compute_tag(words: &[[u8; 16]], h: u128, j0: &[u8; 16], aad: &[u8], text: &[u8]) -> [u8; 16] {
+        let mut y = 0u128;
+        y = ghash(h, y, aad);
+        y = ghash(h, y, text);
+        let lengths = ((aad.len() as u128 * 8) << 64) | (text.len() as u128 * 8);
+        y = gf_mul(y ^ lengths, h);
+
+        (y ^ u128::from_be_bytes(encrypt(words, j0))).to_be_bytes()
+    }
+
+    /// Absorbs `data` into the GHASH accumulator `y`, zero-padding the final block.
+    fn ghash(h: u128, mut y: u128, data: &[u8]) -> u128 {
+        data.chunks(16).for_each(|chunk| {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            y = gf_mul(y ^ u128::from_be_bytes(block), h);
+        });
+        y
+    }
+
+    /// Multiplication in GF(2^128) under the bit-reflected GCM convention.
+    fn gf_mul(x: u128, y: u128) -> u128 {
+        const R: u128 = 0xe1 << 120;
+
+        let mut z = 0u128;
+        let mut v = y;
+        for i in 0..128 {
+            let bit = (x >> (127 - i)) & 1;
+            z ^= v & bit.wrapping_neg();
+            v = (v >> 1) ^ (R & (v & 1).wrapping_neg());
+        }
+        z
     }
 }
 
@@ -149,18 +782,35 @@ fn transpose(state: &[u8; 16]) -> [u8; 16] {
     res
 }
 
-fn key_expansion(key: &[u8; 16]) -> [u32; 44] {
-    let mut words = [0u32; 44];
-    for i in 0..4 {
+/// Expands `key` into one transposed 16-byte round key per round, ready for `add_round_key`.
+fn round_keys(key: &[u8]) -> Vec<[u8; 16]> {
+    key_expansion(key).chunks(4)
+        .map(|chunk| {
+            let mut round_key = [0u8; 16];
+            for i in 0..4 {
+                round_key[(i * 4)..(i * 4 + 4)].copy_from_slice(&chunk[i].to_be_bytes());
+            }
+            transpose(&round_key)
+        })
+        .collect()
+}
+
+fn key_expansion(key: &[u8]) -> Vec<u32> {
+    let nk = key.len() / 4;
+    let nr = nk + 6;
+    let mut words = vec![0u32; 4 * (nr + 1)];
+    for i in 0..nk {
         words[i] = u32::from_be_bytes(key[(i * 4)..(i * 4 + 4)].try_into().unwrap());
     }
-    for i in 4..44 {
+    for i in nk..words.len() {
         let mut temp = words[i - 1];
-        if i % 4 == 0 {
+        if i % nk == 0 {
             temp = u32::from_be_bytes(sub_word(&rot_word(&temp.to_be_bytes())))
-                ^ ((RCON[i / 4] as u32) << 24);
+                ^ ((RCON[i / nk] as u32) << 24);
+        } else if nk > 6 && i % nk == 4 {
+            temp = u32::from_be_bytes(sub_word(&temp.to_be_bytes()));
         }
-        words[i] = words[i - 4] ^ temp;
+        words[i] = words[i - nk] ^ temp;
     }
     words
 }
@@ -175,25 +825,30 @@ fn rot_word(word: &[u8; 4]) -> [u8; 4] {
 }
 
 fn sub_word(word: &[u8; 4]) -> [u8; 4] {
+    if let Some(result) = try_sub_word_aes_ni(word) {
+        return result;
+    }
+
     [
-        SBOX[word[0] as usize],
-        SBOX[word[1] as usize],
-        SBOX[word[2] as usize],
-        SBOX[word[3] as usize],
+        sbox(word[0]),
+        sbox(word[1]),
+        sbox(word[2]),
+        sbox(word[3]),
     ]
 }
 
-fn encrypt(words: &[[u8; 16]], iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
-    let mut state = [0u8; 16];
-    for i in 0..4 {
-        for j in 0..4 {
-            state[i * 4 + j] = text[j * 4 + i] ^ iv[i * 4 + j];
-        }
+fn encrypt(words: &[[u8; 16]], text: &[u8; 16]) -> [u8; 16] {
+    if let Some(result) = try_encrypt_aes_ni(words, text) {
+        return result;
     }
 
+    let mut state = transpose(text);
+
+    let rounds = words.len() - 1;
+
     add_round_key(&mut state, &words[0]);
 
-    words.iter().take(10).skip(1).for_each(|round_key| {
+    words.iter().take(rounds).skip(1).for_each(|round_key| {
         sub_bytes(&mut state);
         shift_rows(&mut state);
         mix_columns(&mut state);
@@ -202,30 +857,25 @@ fn encrypt(words: &[[u8; 16]], iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
 
     sub_bytes(&mut state);
     shift_rows(&mut state);
-    add_round_key(&mut state, &words[10]);
+    add_round_key(&mut state, &words[rounds]);
 
-    *iv = state;
-    for i in 0..4 {
-        for j in 0..4 {
-            state[i * 4 + j] = iv[j * 4 + i];
-        }
-    }
-    state
+    transpose(&state)
 }
 
-fn decrypt(words: &[[u8; 16]], iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
-    let mut state = [0u8; 16];
-    for i in 0..4 {
-        for j in 0..4 {
-            state[i * 4 + j] = text[j * 4 + i];
-        }
+fn decrypt(words: &[[u8; 16]], text: &[u8; 16]) -> [u8; 16] {
+    if let Some(result) = try_decrypt_aes_ni(words, text) {
+        return result;
     }
 
-    add_round_key(&mut state, &words[10]);
+    let mut state = transpose(text);
+
+    let rounds = words.len() - 1;
+
+    add_round_key(&mut state, &words[rounds]);
     inv_shift_rows(&mut state);
     inv_sub_bytes(&mut state);
 
-    words.iter().take(10).skip(1).rev().for_each(|round_key| {
+    words.iter().take(rounds).skip(1).rev().for_each(|round_key| {
         add_round_key(&mut state, round_key);
         inv_mix_columns(&mut state);
         inv_shift_rows(&mut state);
@@ -234,13 +884,107 @@ fn decrypt(words: &[[u8; 16]], iv: &mut [u8; 16], text: &[u8; 16]) -> [u8; 16] {
 
     add_round_key(&mut state, &words[0]);
 
-    for i in 0..4 {
-        for j in 0..4 {
-            state[i * 4 + j] ^= iv[i * 4 + j];
+    transpose(&state)
+}
+
+fn try_encrypt_aes_ni(words: &[[u8; 16]], text: &[u8; 16]) -> Option<[u8; 16]> {
+    #[cfg(target_arch = "x86_64")]
+    if detect_aes_ni() {
+        return Some(unsafe { aes_ni::encrypt(words, text) });
+    }
+
+    let _ = (words, text);
+    None
+}
+
+fn try_decrypt_aes_ni(words: &[[u8; 16]], text: &[u8; 16]) -> Option<[u8; 16]> {
+    #[cfg(target_arch = "x86_64")]
+    if detect_aes_ni() {
+        return Some(unsafe { aes_ni::decrypt(words, text) });
+    }
+
+    let _ = (words, text);
+    None
+}
+
+fn try_sub_word_aes_ni(word: &[u8; 4]) -> Option<[u8; 4]> {
+    #[cfg(target_arch = "x86_64")]
+    if detect_aes_ni() {
+        return Some(unsafe { aes_ni::sub_word(word) });
+    }
+
+    let _ = word;
+    None
+}
+
+fn detect_aes_ni() -> bool {
+    #[cfg(all(target_arch = "x86_64", not(feature = "force_soft")))]
+    {
+        std::is_x86_feature_detected!("aes") && std::is_x86_feature_detected!("ssse3")
+    }
+    #[cfg(not(all(target_arch = "x86_64", not(feature = "force_soft"))))]
+    {
+        false
+    }
+}
+
+/// AES-NI rounds over the same transposed round keys as the portable code.
+#[cfg(target_arch = "x86_64")]
+mod aes_ni {
+    use std::arch::x86_64::*;
+
+    /// Loads a transposed round key back into FIPS-197 byte order with a single shuffle.
+    #[target_feature(enable = "aes,ssse3")]
+    unsafe fn load_round_key(round_key: &[u8; 16]) -> __m128i {
+        unsafe {
+            let transpose = _mm_setr_epi8(0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15);
+            _mm_shuffle_epi8(_mm_loadu_si128(round_key.as_ptr() as *const __m128i), transpose)
+        }
+    }
+
+    #[target_feature(enable = "aes,ssse3")]
+    pub unsafe fn encrypt(words: &[[u8; 16]], text: &[u8; 16]) -> [u8; 16] {
+        unsafe {
+            let rounds = words.len() - 1;
+
+            let mut state = _mm_loadu_si128(text.as_ptr() as *const __m128i);
+            state = _mm_xor_si128(state, load_round_key(&words[0]));
+            for round_key in &words[1..rounds] {
+                state = _mm_aesenc_si128(state, load_round_key(round_key));
+            }
+            state = _mm_aesenclast_si128(state, load_round_key(&words[rounds]));
+
+            let mut res = [0u8; 16];
+            _mm_storeu_si128(res.as_mut_ptr() as *mut __m128i, state);
+            res
+        }
+    }
+
+    /// Uses the equivalent inverse cipher, so the middle round keys go through InvMixColumns.
+    #[target_feature(enable = "aes,ssse3")]
+    pub unsafe fn decrypt(words: &[[u8; 16]], text: &[u8; 16]) -> [u8; 16] {
+        unsafe {
+            let rounds = words.len() - 1;
+
+            let mut state = _mm_loadu_si128(text.as_ptr() as *const __m128i);
+            state = _mm_xor_si128(state, load_round_key(&words[rounds]));
+            for round_key in words[1..rounds].iter().rev() {
+                state = _mm_aesdec_si128(state, _mm_aesimc_si128(load_round_key(round_key)));
+            }
+            state = _mm_aesdeclast_si128(state, load_round_key(&words[0]));
+
+            let mut res = [0u8; 16];
+            _mm_storeu_si128(res.as_mut_ptr() as *mut __m128i, state);
+            res
         }
     }
 
-    state
+    /// AESKEYGENASSIST places SubWord of its second dword in the lowest dword of the result.
+    #[target_feature(enable = "aes,ssse3")]
+    pub unsafe fn sub_word(word: &[u8; 4]) -> [u8; 4] {
+        let x = _mm_set_epi32(0, 0, i32::from_le_bytes(*word), 0);
+        _mm_cvtsi128_si32(_mm_aeskeygenassist_si128::<0>(x)).to_le_bytes()
+    }
 }
 
 fn add_round_key(state: &mut [u8; 16], round_key: &[u8; 16]) {
@@ -250,30 +994,20 @@ fn add_round_key(state: &mut [u8; 16], round_key: &[u8; 16]) {
 }
 
 fn sub_bytes(state: &mut [u8; 16]) {
-    for i in 0..16 {
-        state[i] = SBOX[state[i] as usize];
-    }
+    state.iter_mut().for_each(|b| *b = sbox(*b));
 }
 fn inv_sub_bytes(state: &mut [u8; 16]) {
-    for i in 0..16 {
-        state[i] = INV_SBOX[state[i] as usize];
-    }
+    state.iter_mut().for_each(|b| *b = inv_sbox(*b));
 }
 
 fn shift_rows(state: &mut [u8; 16]) {
-    for i in 0..4 {
-        let temp = state[(i * 4)..(i * 4 + 4)].to_owned();
-        for j in 0..4 {
-            state[i * 4 + j] = temp[(j + i) % 4];
-        }
+    for i in 1..4 {
+        state[(i * 4)..(i * 4 + 4)].rotate_left(i);
     }
 }
 fn inv_shift_rows(state: &mut [u8; 16]) {
     for i in 1..4 {
-        let temp = state[(i * 4)..(i * 4 + 4)].to_owned();
-        for j in 0..4 {
-            state[i * 4 + (j + i) % 4] = temp[j];
-        }
+        state[(i * 4)..(i * 4 + 4)].rotate_right(i);
     }
 }
 
@@ -304,6 +1038,7 @@ fn inv_mix_columns(state: &mut [u8; 16]) {
     }
 }
 
+#[cfg(not(feature = "constant_time"))]
 const GF_TABLE: [u8; 256] = [
     0x01, 0x03, 0x05, 0x0f, 0x11, 0x33, 0x55, 0xff, 0x1a, 0x2e, 0x72, 0x96, 0xa1, 0xf8, 0x13, 0x35,
     0x5f, 0xe1, 0x38, 0x48, 0xd8, 0x73, 0x95, 0xa4, 0xf7, 0x02, 0x06, 0x0a, 0x1e, 0x22, 0x66, 0xaa,
@@ -323,6 +1058,7 @@ const GF_TABLE: [u8; 256] = [
     0x39, 0x4b, 0xdd, 0x7c, 0x84, 0x97, 0xa2, 0xfd, 0x1c, 0x24, 0x6c, 0xb4, 0xc7, 0x52, 0xf6, 0x01,
 ];
 
+#[cfg(not(feature = "constant_time"))]
 const INV_GF_TABLE: [u8; 256] = [
     0x00, 0xff, 0x19, 0x01, 0x32, 0x02, 0x1a, 0xc6, 0x4b, 0xc7, 0x1b, 0x68, 0x33, 0xee, 0xdf, 0x03,
     0x64, 0x04, 0xe0, 0x0e, 0x34, 0x8d, 0x81, 0xef, 0x4c, 0x71, 0x08, 0xc8, 0xf8, 0x69, 0x1c, 0xc1,
@@ -342,9 +1078,471 @@ const INV_GF_TABLE: [u8; 256] = [
     0x67, 0x4a, 0xed, 0xde, 0xc5, 0x31, 0xfe, 0x18, 0x0d, 0x63, 0x8c, 0x80, 0xc0, 0xf7, 0x70, 0x07,
 ];
 
+#[cfg(not(feature = "constant_time"))]
 fn gmul(a: u8, b: u8) -> u8 {
     if a == 0 || b == 0 {
         return 0;
     }
     GF_TABLE[((INV_GF_TABLE[a as usize] as u16 + INV_GF_TABLE[b as usize] as u16) % 255) as usize]
 }
+
+#[cfg(not(feature = "constant_time"))]
+fn sbox(x: u8) -> u8 {
+    SBOX[x as usize]
+}
+#[cfg(not(feature = "constant_time"))]
+fn inv_sbox(x: u8) -> u8 {
+    INV_SBOX[x as usize]
+}
+
+#[cfg(feature = "constant_time")]
+use ct::{gmul, inv_sbox, sbox};
+
+/// Table-free GF(2^8) arithmetic with no secret-dependent branches or memory accesses.
+#[cfg(feature = "constant_time")]
+mod ct {
+    /// Shift-and-add multiplication modulo x^8 + x^4 + x^3 + x + 1, always running all 8 steps.
+    pub fn gmul(a: u8, b: u8) -> u8 {
+        let mut a = a;
+        let mut b = b;
+        let mut p = 0u8;
+        for _ in 0..8 {
+            p ^= a & (b & 1).wrapping_neg();
+            let carry = (a >> 7).wrapping_neg();
+            a = (a << 1) ^ (0x1b & carry);
+            b >>= 1;
+        }
+        p
+    }
+
+    /// Multiplicative inverse as x^254, which also maps 0 to 0.
+    fn inv(x: u8) -> u8 {
+        let x2 = gmul(x, x);
+        let x3 = gmul(x2, x);
+        let x6 = gmul(x3, x3);
+        let x12 = gmul(x6, x6);
+        let x15 = gmul(x12, x3);
+        let x30 = gmul(x15, x15);
+        let x60 = gmul(x30, x30);
+        let x120 = gmul(x60, x60);
+        let x240 = gmul(x120, x120);
+        let x252 = gmul(x240, x12);
+        gmul(x252, x2)
+    }
+
+    pub fn sbox(x: u8) -> u8 {
+        let b = inv(x);
+        b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4) ^ 0x63
+    }
+
+    pub fn inv_sbox(x: u8) -> u8 {
+        inv(x.rotate_left(1) ^ x.rotate_left(3) ^ x.rotate_left(6) ^ 0x05)
+    }
+}
+
+/// AES Key Wrap (RFC 3394) and AES Key Wrap with Padding (RFC 5649).
+mod keywrap {
+    use crate::{Error, decrypt, encrypt};
+
+    const DEFAULT_IV: [u8; 8] = [0xa6; 8];
+    /// The RFC 5649 alternative IV is this prefix followed by the 32-bit message length.
+    const AIV_PREFIX: [u8; 4] = [0xa6, 0x59, 0x59, 0xa6];
+
+    /// Wraps key data of at least 16 bytes whose length is a multiple of 8.
+    pub fn wrap(words: &[[u8; 16]], text: &[u8]) -> Result<Vec<u8>, Error> {
+        if text.len() < 16 || !text.len().is_multiple_of(8) {
+            return Err(Error::KeyDataLength(text.len()));
+        }
+        Ok(wrap_blocks(words, DEFAULT_IV, text))
+    }
+
+    pub fn unwrap(words: &[[u8; 16]], text: &[u8]) -> Result<Vec<u8>, Error> {
+        if text.len() < 24 || !text.len().is_multiple_of(8) {
+            return Err(Error::KeyDataLength(text.len()));
+        }
+        let (a, result) = unwrap_blocks(words, text);
+        if !ct_eq(&a, &DEFAULT_IV) {
+            return Err(Error::IntegrityCheck);
+        }
+        Ok(result)
+    }
+
+    /// Wraps key data of any non-zero length, zero-padding it to a multiple of 8.
+    pub fn wrap_pad(words: &[[u8; 16]], text: &[u8]) -> Result<Vec<u8>, Error> {
+        if text.is_empty() || text.len() > u32::MAX as usize {
+            return Err(Error::KeyDataLength(text.len()));
+        }
+
+        let mut aiv = [0u8; 8];
+        aiv[..4].copy_from_slice(&AIV_PREFIX);
+        aiv[4..].copy_from_slice(&(text.len() as u32).to_be_bytes());
+
+        let mut padded = text.to_vec();
+        padded.resize(text.len().div_ceil(8) * 8, 0);
+
+        // A single padded block is encrypted directly together with the AIV.
+        if padded.len() == 8 {
+            let mut block = [0u8; 16];
+            block[..8].copy_from_slice(&aiv);
+            block[8..].copy_from_slice(&padded);
+            return Ok(encrypt(words, &block).to_vec());
+        }
+        Ok(wrap_blocks(words, aiv, &padded))
+    }
+
+    pub fn unwrap_pad(words: &[[u8; 16]], text: &[u8]) -> Result<Vec<u8>, Error> {
+        if text.len() < 16 || !text.len().is_multiple_of(8) {
+            return Err(Error::KeyDataLength(text.len()));
+        }
+
+        let (a, padded) = if text.len() == 16 {
+            let block = decrypt(words, text.try_into().unwrap());
+            (block[..8].try_into().unwrap(), block[8..].to_vec())
+        } else {
+            unwrap_blocks(words, text)
+        };
+
+        let len = u32::from_be_bytes(a[4..].try_into().unwrap()) as usize;
+        let valid = ct_eq(&a[..4], &AIV_PREFIX)
+            && len <= padded.len()
+            && len + 8 > padded.len()
+            && padded[len..].iter().all(|&b| b == 0);
+        if !valid {
+            return Err(Error::IntegrityCheck);
+        }
+        Ok(padded[..len].to_vec())
+    }
+
+    /// The wrapping process W of RFC 3394 section 2.2.1, using the index-based form.
+    fn wrap_blocks(words: &[[u8; 16]], iv: [u8; 8], text: &[u8]) -> Vec<u8> {
+        let mut a = iv;
+        let mut r = text.chunks(8).map(|chunk| chunk.try_into().unwrap()).collect::<Vec<[u8; 8]>>();
+        let n = r.len();
+
+        for j in 0..6 {
+            for (i, ri) in r.iter_mut().enumerate() {
+                let mut block = [0u8; 16];
+                block[..8].copy_from_slice(&a);
+                block[8..].copy_from_slice(ri);
+                let b = encrypt(words, &block);
+
+                let t = (n * j + i + 1) as u64;
+                a = (u64::from_be_bytes(b[..8].try_into().unwrap()) ^ t).to_be_bytes();
+                *ri = b[8..].try_into().unwrap();
+            }
+        }
+
+        let mut result = a.to_vec();
+        r.iter().for_each(|block| result.extend_from_slice(block));
+        result
+    }
+
+    /// The unwrapping process W^-1, returning the recovered IV and key data.
+    fn unwrap_blocks(words: &[[u8; 16]], text: &[u8]) -> ([u8; 8], Vec<u8>) {
+        let mut a: [u8; 8] = text[..8].try_into().unwrap();
+        let mut r = text[8..].chunks(8).map(|chunk| chunk.try_into().unwrap()).collect::<Vec<[u8; 8]>>();
+        let n = r.len();
+
+        for j in (0..6).rev() {
+            for (i, ri) in r.iter_mut().enumerate().rev() {
+                let t = (n * j + i + 1) as u64;
+                let mut block = [0u8; 16];
+                block[..8].copy_from_slice(&(u64::from_be_bytes(a) ^ t).to_be_bytes());
+                block[8..].copy_from_slice(ri);
+                let b = decrypt(words, &block);
+
+                a = b[..8].try_into().unwrap();
+                *ri = b[8..].try_into().unwrap();
+            }
+        }
+
+        (a, r.concat())
+    }
+
+    fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+        a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+    }
+}
+
+/// AES-CMAC (RFC 4493, NIST SP 800-38B) with full 128-bit tags.
+mod cmac {
+    use crate::{encrypt, gf_double, xor_block};
+
+    pub fn mac(words: &[[u8; 16]], text: &[u8]) -> [u8; 16] {
+        let (k1, k2) = subkeys(words);
+
+        // The empty message still occupies one (padded) block.
+        let n = text.len().div_ceil(16).max(1);
+        let mut x = [0u8; 16];
+        for chunk in text.chunks(16).take(n - 1) {
+            x = encrypt(words, &xor_block(&x, chunk.try_into().unwrap()));
+        }
+
+        let tail = &text[16 * (n - 1)..];
+        let last = if tail.len() == 16 {
+            xor_block(tail.try_into().unwrap(), &k1)
+        } else {
+            let mut block = [0u8; 16];
+            block[..tail.len()].copy_from_slice(tail);
+            block[tail.len()] = 0x80;
+            xor_block(&block, &k2)
+        };
+        encrypt(words, &xor_block(&x, &last))
+    }
+
+    /// Recomputes the tag over `text` and compares it with `tag` in constant time.
+    pub fn verify(words: &[[u8; 16]], text: &[u8], tag: &[u8; 16]) -> bool {
+        let expected = mac(words, text);
+        expected.iter().zip(tag.iter()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+    }
+
+    /// K1 and K2 are successive doublings of L = E_K(0^128), read as big-endian polynomials.
+    fn subkeys(words: &[[u8; 16]]) -> ([u8; 16], [u8; 16]) {
+        let l = u128::from_be_bytes(encrypt(words, &[0u8; 16]));
+        let k1 = gf_double(l);
+        let k2 = gf_double(k1);
+        (k1.to_be_bytes(), k2.to_be_bytes())
+    }
+}
+
+/// XTS-AES (IEEE 1619) over a single data unit, with ciphertext stealing for partial final blocks.
+mod xts {
+    use crate::{Error, decrypt, encrypt, gf_double, xor_block};
+
+    /// Encrypts one data unit of at least 16 bytes; `tweak` is the 128-bit data unit number.
+    pub fn encrypt_unit(words: &[[u8; 16]], tweak_words: &[[u8; 16]], tweak: &[u8; 16], text: &[u8]) -> Result<Vec<u8>, Error> {
+        process(words, tweak_words, tweak, text, false)
+    }
+
+    pub fn decrypt_unit(words: &[[u8; 16]], tweak_words: &[[u8; 16]], tweak: &[u8; 16], text: &[u8]) -> Result<Vec<u8>, Error> {
+        process(words, tweak_words, tweak, text, true)
+    }
+
+    fn process(
+        words: &[[u8; 16]],
+        tweak_words: &[[u8; 16]],
+        tweak: &[u8; 16],
+        text: &[u8],
+        inverse: bool,
+    ) -> Result<Vec<u8>, Error> {
+        if text.len() < 16 {
+            return Err(Error::DataUnitLength(text.len()));
+        }
+        let cipher = if inverse { decrypt } else { encrypt };
+
+        // The tweak is multiplied by alpha once per block, with the block read as a little-endian polynomial.
+        let mut t = u128::from_le_bytes(encrypt(tweak_words, tweak));
+        let tail = text.len() % 16;
+        let full = text.len() / 16 - usize::from(tail != 0);
+
+        let mut result = Vec::with_capacity(text.len());
+        for chunk in text[..16 * full].chunks(16) {
+            result.extend_from_slice(&apply(words, t, chunk.try_into().unwrap(), cipher));
+            t = gf_double(t);
+        }
+
+        if tail != 0 {
+            // Ciphertext stealing: the last full block is processed under the following tweak when
+            // decrypting, so the tweak order of the final two blocks is swapped relative to encryption.
+            let (first, second) = if inverse { (gf_double(t), t) } else { (t, gf_double(t)) };
+
+            let last = &text[16 * full..];
+            let cc = apply(words, first, last[..16].try_into().unwrap(), cipher);
+            let mut pp = cc;
+            pp[..tail].copy_from_slice(&last[16..]);
+
+            result.extend_from_slice(&apply(words, second, &pp, cipher));
+            result.extend_from_slice(&cc[..tail]);
+        }
+
+        Ok(result)
+    }
+
+    fn apply(words: &[[u8; 16]], t: u128, block: &[u8; 16], cipher: fn(&[[u8; 16]], &[u8; 16]) -> [u8; 16]) -> [u8; 16] {
+        let t = t.to_le_bytes();
+        xor_block(&cipher(words, &xor_block(block, &t)), &t)
+    }
+}
+
+/// SHA-256 (FIPS 180-4), copied from `SHA256` in lab/04/code-1 and cut down to what PBKDF2
+/// uses: the portable compression function behind streaming `update`/`finalize`, without
+/// SHA-224, the SHA-NI backend or the length-extension helpers. It is a copy rather than a
+/// shared module because this lab is built and submitted to the judge as a single file;
+/// fixes to either copy should be mirrored in the other.
+mod sha256 {
+    #[derive(Clone)]
+    pub struct SHA256 {
+        buffer: [u8; 64],
+        buffer_len: usize,
+        h: [u32; 8],
+        len: usize,
+    }
+
+    impl SHA256 {
+        pub fn new() -> Self {
+            SHA256 {
+                buffer: [0u8; 64],
+                buffer_len: 0,
+                h: H,
+                len: 0,
+            }
+        }
+
+        pub fn update(&mut self, mut text: &[u8]) {
+            self.len += text.len();
+
+            if self.buffer_len > 0 {
+                let take = text.len().min(64 - self.buffer_len);
+                self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&text[..take]);
+                self.buffer_len += take;
+                text = &text[take..];
+                if self.buffer_len < 64 {
+                    return;
+                }
+                let temp = self.buffer;
+                self.process_chunk(&temp);
+                self.buffer_len = 0;
+            }
+
+            let mut chunks = text.chunks_exact(64);
+            chunks.by_ref().for_each(|chunk| self.process_chunk(chunk));
+            let rest = chunks.remainder();
+            self.buffer[..rest.len()].copy_from_slice(rest);
+            self.buffer_len = rest.len();
+        }
+
+        pub fn finalize(&mut self) -> [u8; 32] {
+            self.buffer[self.buffer_len] = 0x80;
+            self.buffer_len += 1;
+
+            if self.buffer_len > 56 {
+                self.buffer[self.buffer_len..].fill(0);
+                let temp = self.buffer;
+                self.process_chunk(&temp);
+                self.buffer_len = 0;
+            }
+
+            self.buffer[self.buffer_len..56].fill(0);
+
+            let len = self.len as u64 * 8;
+            self.buffer[56..64].copy_from_slice(&len.to_be_bytes());
+            let temp = self.buffer;
+            self.process_chunk(&temp);
+
+            let mut res = [0u8; 32];
+            self.h.iter().enumerate().for_each(|(i, &word)| {
+                res[i * 4..(i + 1) * 4].copy_from_slice(&word.to_be_bytes());
+            });
+            res
+        }
+
+        pub fn digest(text: &[u8]) -> [u8; 32] {
+            let mut ctx = SHA256::new();
+            ctx.update(text);
+            ctx.finalize()
+        }
+
+        fn process_chunk(&mut self, chunk: &[u8]) {
+            let mut w = [0u32; 64];
+            chunk.chunks(4).enumerate().for_each(|(i, bytes)| {
+                w[i] = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            });
+
+            for i in 16..64 {
+                let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+                let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+                w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+            }
+
+            let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.h;
+
+            for i in 0..64 {
+                let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+                let ch = (e & f) ^ (!e & g);
+                let temp1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+                let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+                let maj = (a & b) ^ (a & c) ^ (b & c);
+                let temp2 = s0.wrapping_add(maj);
+
+                h = g;
+                g = f;
+                f = e;
+                e = d.wrapping_add(temp1);
+                d = c;
+                c = b;
+                b = a;
+                a = temp1.wrapping_add(temp2);
+            }
+
+            [a, b, c, d, e, f, g, h].iter().enumerate().for_each(|(i, &word)| {
+                self.h[i] = self.h[i].wrapping_add(word);
+            });
+        }
+    }
+
+    const H: [u32; 8] = [
+        0x6a09e667,
+        0xbb67ae85,
+        0x3c6ef372,
+        0xa54ff53a,
+        0x510e527f,
+        0x9b05688c,
+        0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+        0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+        0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+        0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+        0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+        0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+        0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+    ];
+}
+
+/// PBKDF2 (RFC 8018) with HMAC-SHA256 (RFC 2104) as the pseudorandom function.
+mod pbkdf2 {
+    use crate::sha256::SHA256;
+
+    /// `iterations` must be at least 1; callers reject 0 before deriving.
+    pub fn pbkdf2_hmac_sha256(password: &[u8], salt: &[u8], iterations: u32, len: usize) -> Vec<u8> {
+        debug_assert!(iterations >= 1);
+        let mut key = [0u8; 64];
+        if password.len() > 64 {
+            key[..32].copy_from_slice(&SHA256::digest(password));
+        } else {
+            key[..password.len()].copy_from_slice(password);
+        }
+
+        // The padded keys are absorbed once; every PRF call clones these midstates.
+        let mut inner = SHA256::new();
+        inner.update(&key.map(|b| b ^ 0x36));
+        let mut outer = SHA256::new();
+        outer.update(&key.map(|b| b ^ 0x5c));
+
+        let prf = |parts: &[&[u8]]| -> [u8; 32] {
+            let mut ctx = inner.clone();
+            parts.iter().for_each(|part| ctx.update(part));
+            let digest = ctx.finalize();
+            let mut ctx = outer.clone();
+            ctx.update(&digest);
+            ctx.finalize()
+        };
+
+        (1..=len.div_ceil(32) as u32)
+            .flat_map(|block| {
+                let mut u = prf(&[salt, &block.to_be_bytes()]);
+                let mut t = u;
+                for _ in 1..iterations {
+                    u = prf(&[&u]);
+                    t.iter_mut().zip(u).for_each(|(a, b)| *a ^= b);
+                }
+                t
+            })
+            .take(len)
+            .collect()
+    }
+}
@@ -0,0 +1,97 @@
+import os
+import re
+import subprocess
+import sys
+
+# Known-answer checks for every SHA-2 variant selectable with `-a`.
+#
+# The FIPS 180-4 example messages ("abc" and the two-block messages) are
+# hashed with each algorithm, then the byte-oriented Len/Msg/MD entries of
+# CAVP-format response files are replayed against the binary. With no
+# arguments these are the SHA*{ShortMsg,LongMsg}.rsp files in sample/cavp/
+# (written by sample/cavp_gen.py; the NIST files can replace them), and a
+# missing file is an error. Otherwise each argument is a response file.
+#
+# Run from the crate root with:
+#     python3 sample/cavp.py [SHA*Msg.rsp ...]
+examples = {
+    "sha224": [
+        (b"abc", "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"),
+        (b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+         "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525"),
+    ],
+    "sha256": [
+        (b"abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        (b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+         "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"),
+    ],
+    "sha384": [
+        (b"abc", "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed"
+                 "8086072ba1e7cc2358baeca134c825a7"),
+        (b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopq"
+         b"klmnopqrlmnopqrsmnopqrstnopqrstu",
+         "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712"
+         "fcc7c71a557e2db966c3e9fa91746039"),
+    ],
+    "sha512": [
+        (b"abc", "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a"
+                 "2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"),
+        (b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopq"
+         b"klmnopqrlmnopqrsmnopqrstnopqrstu",
+         "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018"
+         "501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"),
+    ],
+    "sha512-256": [
+        (b"abc", "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"),
+        (b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopq"
+         b"klmnopqrlmnopqrsmnopqrstnopqrstu",
+         "3928e184fb8690f840da3988121d31be65cb9d3ef83ee6146feac861e19b563a"),
+    ],
+}
+
+# CAVP file names spell the algorithm without the hyphen.
+file_algorithms = {
+    "SHA224": "sha224",
+    "SHA256": "sha256",
+    "SHA384": "sha384",
+    "SHA512": "sha512",
+    "SHA512_256": "sha512-256",
+}
+
+
+def parse_rsp(path):
+    name = re.match(r"(SHA\d+(?:_\d+)?)(?:Short|Long)Msg", path.split("/")[-1]).group(1)
+    vectors, length, msg = [], None, None
+    with open(path) as file:
+        for line in file:
+            key, _, value = (part.strip() for part in line.partition("="))
+            if key == "Len":
+                length = int(value)
+            elif key == "Msg":
+                msg = bytes.fromhex(value)[:length // 8]
+            elif key == "MD":
+                vectors.append((msg, value))
+    return file_algorithms[name], vectors
+
+
+subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+
+paths = sys.argv[1:]
+if not paths:
+    paths = [f"sample/cavp/{name}{kind}.rsp" for name in file_algorithms for kind in ("ShortMsg", "LongMsg")]
+    missing = [path for path in paths if not os.path.exists(path)]
+    if missing:
+        sys.exit(f"missing response files: {', '.join(missing)} (run sample/cavp_gen.py)")
+
+suites = list(examples.items()) + [parse_rsp(path) for path in paths]
+
+count = 0
+for algorithm, vectors in suites:
+    for msg, expected in vectors:
+        output = subprocess.run(
+            ["target/release/code-1", "-a", algorithm], input=msg, capture_output=True, check=True,
+        ).stdout.split()[0].decode()
+        assert output == expected, f"{algorithm} over {len(msg)} bytes: got {output}"
+        count += 1
+
+print(f"{count} vectors from {len(paths)} response files and the FIPS 180-4 examples OK")
@@ -0,0 +1,56 @@
+import hashlib
+import os
+import random
+
+# Writes sample/cavp/SHA*{ShortMsg,LongMsg}.rsp in the layout of the NIST CAVP
+# SHAVS byte-oriented response files, with digests from Python's hashlib
+# (OpenSSL), an implementation independent of ours. The official NIST files
+# use the same names and can replace these as they are.
+#
+# ShortMsg covers every length from 0 to one block in bytes, as SHAVS does.
+# LongMsg covers one byte either side of several block multiples, the padding
+# boundary (56 or 112 bytes into a block) and SHAVS-style steps of 99 bytes.
+#
+# Run from the crate root with:
+#     python3 sample/cavp_gen.py
+ALGORITHMS = {
+    # file name: (hashlib name, block size in bytes)
+    "SHA224": ("sha224", 64),
+    "SHA256": ("sha256", 64),
+    "SHA384": ("sha384", 128),
+    "SHA512": ("sha512", 128),
+    "SHA512_256": ("sha512_256", 128),
+}
+HEADER = """\
+#  Generated by sample/cavp_gen.py with Python's hashlib, in the layout of the
+#  NIST CAVP {name} response file. Not the NIST file; that one can replace it.
+"""
+
+
+def long_lengths(block):
+    lengths = {block * k + d for k in (1, 2, 3, 4, 8, 16) for d in (-1, 0, 1)}
+    lengths |= {block * k + block - 8 - 1 + d for k in (1, 5) for d in (0, 1)}
+    lengths |= {block + 99 * i for i in range(1, 33)}
+    return sorted(lengths)
+
+
+def write(path, name, digest_size, entries):
+    with open(path, "w") as f:
+        f.write(HEADER.format(name=name))
+        f.write(f"\n[L = {digest_size}]\n")
+        for msg, md in entries:
+            # SHAVS writes a single 00 byte for the empty message.
+            f.write(f"\nLen = {len(msg) * 8}\nMsg = {msg.hex() or '00'}\nMD = {md}\n")
+
+
+rng = random.Random(2025)
+os.makedirs("sample/cavp", exist_ok=True)
+for name, (algorithm, block) in ALGORITHMS.items():
+    digest_size = hashlib.new(algorithm).digest_size
+    for kind, lengths in [("ShortMsg", range(block + 1)), ("LongMsg", long_lengths(block))]:
+        entries = []
+        for length in lengths:
+            msg = rng.randbytes(length)
+            entries.append((msg, hashlib.new(algorithm, msg).hexdigest()))
+        write(f"sample/cavp/{name}{kind}.rsp", f"{name}{kind}", digest_size, entries)
+        print(f"wrote {name}{kind}.rsp: {len(entries)} entries")
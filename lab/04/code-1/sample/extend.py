@@ -0,0 +1,35 @@
+import hashlib
+import random
+import subprocess
+import sys
+
+# Length-extension check for --extend. For random secrets and messages, only the
+# digest of secret || message and its length are passed in; the forged digest
+# must equal SHA-256 over secret || message || extension, where the extension
+# (glue padding followed by the suffix) is what the tool says to append.
+#
+# Run from the crate root with:
+#     python3 sample/extend.py [rounds]
+subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+
+rng = random.Random(2025)
+rounds = int(sys.argv[1]) if len(sys.argv) > 1 else 100
+
+for i in range(rounds):
+    secret = rng.randbytes(rng.randint(0, 64))
+    message = rng.randbytes(rng.randint(0, 150))
+    suffix = rng.randbytes(rng.randint(0, 150))
+    digest = hashlib.sha256(secret + message).hexdigest()
+
+    output = subprocess.run([
+        "target/release/code-1", "--extend", digest,
+        "--original-length", str(len(secret + message)),
+        "--append", suffix.hex(),
+    ], capture_output=True, check=True, text=True).stdout
+    forged, extension = output.split()
+    extension = bytes.fromhex(extension)
+
+    assert extension.endswith(suffix), f"extension does not end with the suffix in round {i}"
+    assert hashlib.sha256(secret + message + extension).hexdigest() == forged, f"forgery failed in round {i}"
+
+print(f"{rounds} rounds OK")
@@ -0,0 +1,88 @@
+import hashlib
+import hmac
+import subprocess
+
+# HMAC-SHA256 test cases 1-7 from RFC 4231 section 4 and the SHA-256 HKDF test
+# cases 1-3 from RFC 5869 appendix A. Test case 5 of RFC 4231 checks a tag
+# truncated to 128 bits, so it goes through --verify; every tag is also checked
+# to fail verification once a bit is flipped. The same RFC 4231 inputs are run
+# with -a sha512 against Python's hmac module, and --hmac with any other
+# algorithm must be refused.
+#
+# Run from the crate root with:
+#     python3 sample/mac.py
+hmac_vectors = [
+    ("0b" * 20, b"Hi There".hex(),
+     "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"),
+    (b"Jefe".hex(), b"what do ya want for nothing?".hex(),
+     "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"),
+    ("aa" * 20, "dd" * 50,
+     "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe"),
+    ("0102030405060708090a0b0c0d0e0f10111213141516171819", "cd" * 50,
+     "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b"),
+    ("0c" * 20, b"Test With Truncation".hex(),
+     "a3b6167473100ee06e0c796c2955552b"),
+    ("aa" * 131, b"Test Using Larger Than Block-Size Key - Hash Key First".hex(),
+     "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"),
+    ("aa" * 131, b"This is a test using a larger than block-size key and a larger than block-size data. "
+                 b"The key needs to be hashed before being used by the HMAC algorithm.".hex(),
+     "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2"),
+]
+
+hkdf_vectors = [
+    ("0b" * 22, "000102030405060708090a0b0c", "f0f1f2f3f4f5f6f7f8f9", 42,
+     "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf"
+     "34007208d5b887185865"),
+    (bytes(range(0x00, 0x50)).hex(), bytes(range(0x60, 0xb0)).hex(), bytes(range(0xb0, 0x100)).hex(), 82,
+     "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c"
+     "59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71"
+     "cc30c58179ec3e87c14c01d5c1f3434f1d87"),
+    ("0b" * 22, "", "", 42,
+     "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d"
+     "9d201395faa4b61a96c8"),
+]
+
+subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+binary = "target/release/code-1"
+
+for i, (key, data, tag) in enumerate(hmac_vectors, 1):
+    if len(tag) == 64:
+        output = subprocess.run(
+            [binary, "--hmac", key], input=bytes.fromhex(data), capture_output=True, check=True,
+        ).stdout.split()[0].decode()
+        assert output == tag, f"RFC 4231 test case {i}: got {output}"
+
+    assert subprocess.run([binary, "--hmac", key, "--verify", tag], input=bytes.fromhex(data)).returncode == 0
+    tampered = tag[:-1] + format(int(tag[-1], 16) ^ 1, "x")
+    assert subprocess.run(
+        [binary, "--hmac", key, "--verify", tampered], input=bytes.fromhex(data), capture_output=True,
+    ).returncode == 1, f"RFC 4231 test case {i}: tampered tag verified"
+
+for i, (key, data, _) in enumerate(hmac_vectors, 1):
+    tag = hmac.new(bytes.fromhex(key), bytes.fromhex(data), hashlib.sha512).hexdigest()
+    output = subprocess.run(
+        [binary, "-a", "sha512", "--tag", "--hmac", key], input=bytes.fromhex(data), capture_output=True, check=True,
+    ).stdout.decode().strip()
+    assert output == f"HMAC-SHA512 (-) = {tag}", f"RFC 4231 test case {i}, SHA-512: got {output}"
+
+    for expected in [tag, tag[:32]]:
+        assert subprocess.run(
+            [binary, "-a", "sha512", "--hmac", key, "--verify", expected], input=bytes.fromhex(data),
+        ).returncode == 0
+    tampered = tag[:-1] + format(int(tag[-1], 16) ^ 1, "x")
+    assert subprocess.run(
+        [binary, "-a", "sha512", "--hmac", key, "--verify", tampered], input=bytes.fromhex(data), capture_output=True,
+    ).returncode == 1, f"RFC 4231 test case {i}, SHA-512: tampered tag verified"
+
+for algorithm in ["sha224", "sha384", "sha512-256"]:
+    output = subprocess.run([binary, "-a", algorithm, "--hmac", "00"], input=b"", capture_output=True)
+    assert output.returncode != 0 and output.stdout == b"", f"--hmac accepted with {algorithm}"
+
+for i, (ikm, salt, info, length, okm) in enumerate(hkdf_vectors, 1):
+    args = [binary, "--hkdf", ikm, "--length", str(length)]
+    args += ["--salt", salt] if salt else []
+    args += ["--info", info] if info else []
+    output = subprocess.run(args, capture_output=True, check=True, text=True).stdout.strip()
+    assert output == okm, f"RFC 5869 test case {i}: got {output}"
+
+print(f"{len(hmac_vectors)} HMAC-SHA256, {len(hmac_vectors)} HMAC-SHA512 and {len(hkdf_vectors)} HKDF vectors OK")
@@ -0,0 +1,88 @@
+import hashlib
+import os
+import random
+import subprocess
+import sys
+import tempfile
+
+# Checks --merkle against a direct transcription of the RFC 6962 definitions of
+# the Merkle tree hash MTH and the audit path PATH, for random inputs and chunk
+# sizes. Every proof must pass --merkle-verify, and must fail once the chunk is
+# altered.
+#
+# Run from the crate root with:
+#     python3 sample/merkle.py [rounds]
+
+
+def sha256(data):
+    return hashlib.sha256(data).digest()
+
+
+def split(n):
+    k = 1
+    while k * 2 < n:
+        k *= 2
+    return k
+
+
+def mth(leaves):
+    if len(leaves) == 0:
+        return sha256(b"")
+    if len(leaves) == 1:
+        return sha256(b"\x00" + leaves[0])
+    k = split(len(leaves))
+    return sha256(b"\x01" + mth(leaves[:k]) + mth(leaves[k:]))
+
+
+def path(m, leaves):
+    if len(leaves) <= 1:
+        return []
+    k = split(len(leaves))
+    if m < k:
+        return path(m, leaves[:k]) + [mth(leaves[k:])]
+    return path(m - k, leaves[k:]) + [mth(leaves[:k])]
+
+
+# The eight-leaf tree from the Certificate Transparency reference test data.
+reference = [bytes.fromhex(leaf) for leaf in
+             ["", "00", "10", "2021", "3031", "40414243", "5051525354555657", "606162636465666768696a6b6c6d6e6f"]]
+assert mth(reference).hex() == "5dc9da79a70659a9ad559cb701ded9a2ab9d823aad2f4960cfe370eff4604328"
+
+subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+binary = "target/release/code-1"
+
+rng = random.Random(2025)
+rounds = int(sys.argv[1]) if len(sys.argv) > 1 else 100
+
+with tempfile.TemporaryDirectory() as tmp:
+    data_path, proof_path, chunk_path = (os.path.join(tmp, name) for name in ("data", "proof", "chunk"))
+    for i in range(rounds):
+        chunk_size = rng.randint(1, 64)
+        data = rng.randbytes(rng.randint(0, 40 * chunk_size))
+        leaves = [data[j:j + chunk_size] for j in range(0, len(data), chunk_size)]
+        with open(data_path, "wb") as file:
+            file.write(data)
+
+        args = [binary, "--merkle", "--chunk-size", str(chunk_size), data_path]
+        if not leaves:
+            output = subprocess.run(args, capture_output=True, check=True, text=True).stdout
+            assert output.split()[0] == mth(leaves).hex(), f"empty root mismatch in round {i}"
+            continue
+
+        m = rng.randrange(len(leaves))
+        output = subprocess.run(args + ["--prove", str(m)], capture_output=True, check=True, text=True).stdout
+        lines = output.splitlines()
+        assert lines[0].split()[0] == mth(leaves).hex(), f"root mismatch in round {i}"
+        assert lines[1].split() == [str(m), str(len(leaves)), sha256(b"\x00" + leaves[m]).hex()]
+        assert lines[2:] == [h.hex() for h in path(m, leaves)], f"audit path mismatch in round {i}"
+
+        with open(proof_path, "w") as file:
+            file.write(output)
+        with open(chunk_path, "wb") as file:
+            file.write(leaves[m])
+        assert subprocess.run([binary, "--merkle-verify", proof_path, chunk_path], capture_output=True).returncode == 0
+        with open(chunk_path, "wb") as file:
+            file.write(leaves[m] + b"\x00")
+        assert subprocess.run([binary, "--merkle-verify", proof_path, chunk_path], capture_output=True).returncode == 1
+
+print(f"{rounds} rounds OK")
@@ -0,0 +1,30 @@
+import hashlib
+import random
+import subprocess
+import sys
+
+# Checks --mine on random prefixes, including ones longer than a chunk so that
+# the precomputed midstate covers whole chunks. Every reported nonce must give a
+# digest with the requested number of leading zero bits when hashed from scratch.
+#
+# Run from the crate root with:
+#     python3 sample/mine.py [rounds]
+subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+
+rng = random.Random(2025)
+rounds = int(sys.argv[1]) if len(sys.argv) > 1 else 20
+
+for i in range(rounds):
+    prefix = rng.randbytes(rng.choice([12, rng.randint(0, 200)]))
+    difficulty = rng.randint(8, 18)
+    output = subprocess.run(
+        ["target/release/code-1", "--mine", prefix.hex(), "--difficulty", str(difficulty), "--threads", "2"],
+        capture_output=True, check=True, text=True,
+    ).stdout
+    nonce, digest = output.split()
+
+    expected = hashlib.sha256(prefix + bytes.fromhex(nonce)).digest()
+    assert digest == expected.hex(), f"digest mismatch in round {i}"
+    assert int.from_bytes(expected, "big") >> (256 - difficulty) == 0, f"difficulty not met in round {i}"
+
+print(f"{rounds} rounds OK")
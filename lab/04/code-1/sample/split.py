@@ -0,0 +1,41 @@
+import random
+import subprocess
+import sys
+import time
+
+# Incremental hashing must not depend on how the input is split across reads.
+# Each message is written to the binary's stdin in random pieces, pausing after
+# each one so that every `read` returns a single piece, and the digest must match
+# hashing the whole message in one write.
+#
+# Run from the crate root with:
+#     python3 sample/split.py [rounds]
+subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+
+
+def run(algorithm, pieces):
+    proc = subprocess.Popen(
+        ["target/release/code-1", "-a", algorithm], stdin=subprocess.PIPE, stdout=subprocess.PIPE,
+    )
+    for piece in pieces:
+        proc.stdin.write(piece)
+        proc.stdin.flush()
+        time.sleep(0.002)
+    proc.stdin.close()
+    output = proc.stdout.read()
+    assert proc.wait() == 0
+    return output
+
+
+rng = random.Random(2025)
+rounds = int(sys.argv[1]) if len(sys.argv) > 1 else 100
+
+for i in range(rounds):
+    algorithm = rng.choice(["sha224", "sha256", "sha384", "sha512", "sha512-256"])
+    data = rng.randbytes(rng.randint(0, 600))
+    cuts = sorted(rng.sample(range(len(data) + 1), rng.randint(0, min(12, len(data) + 1))))
+    pieces = [data[a:b] for a, b in zip([0] + cuts, cuts + [len(data)]) if b > a]
+
+    assert run(algorithm, pieces) == run(algorithm, [data]), f"{algorithm} split {cuts} differs in round {i}"
+
+print(f"{rounds} rounds OK")
@@ -1,19 +1,555 @@
 use std::io;
 use std::io::{Read, Write};
+use std::time::Instant;
+use std::{env, fs, process, thread};
 
 fn main() {
-    let mut ctx = SHA256::new();
-    loop {
-        let mut buf = [0u8; 640000];
-        let n = io::stdin().read(&mut buf).unwrap();
-        if n == 0 {
-            break;
+    if cfg!(feature = "online_judge") {
+        let mut ctx = SHA256::new();
+        loop {
+            let mut buf = [0u8; 640000];
+            let n = io::stdin().read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            ctx.update(&buf[..n]);
+        }
+        io::stdout().write_all(&ctx.finalize()).unwrap();
+        return;
+    }
+
+    let mut options = Options {
+        algorithm: String::from("sha256"),
+        hmac_key: None,
+        tag: false,
+        quiet: false,
+        status: false,
+    };
+    let mut check = false;
+    let mut paths = Vec::new();
+    let mut expected_tag = None;
+    let mut hkdf_ikm = None;
+    let mut salt = Vec::new();
+    let mut info = Vec::new();
+    let mut length = 32;
+    let mut mine_prefix = None;
+    let mut extend_digest = None;
+    let mut original_length = None;
+    let mut suffix = Vec::new();
+    let mut merkle = false;
+    let mut chunk_size = 4096;
+    let mut prove = None;
+    let mut proof_path = None;
+    let mut difficulty = 32;
+    let mut threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "-a" | "--algorithm" => options.algorithm = value(),
+            "-c" | "--check" => check = true,
+            "--tag" => options.tag = true,
+            "--quiet" => options.quiet = true,
+            "--status" => options.status = true,
+            "--hmac" => options.hmac_key = Some(parse_hex(&value())),
+            "--verify" => expected_tag = Some(parse_hex(&value())),
+            "--hkdf" => hkdf_ikm = Some(parse_hex(&value())),
+            "--salt" => salt = parse_hex(&value()),
+            "--info" => info = parse_hex(&value()),
+            "--length" => length = value().parse().unwrap_or_else(|_| usage()),
+            "--mine" => mine_prefix = Some(parse_hex(&value())),
+            "--difficulty" => difficulty = value().parse().unwrap_or_else(|_| usage()),
+            "--threads" => threads = value().parse().unwrap_or_else(|_| usage()),
+            "--extend" => extend_digest = Some(parse_hex(&value())),
+            "--original-length" => original_length = Some(value().parse().unwrap_or_else(|_| usage())),
+            "--append" => suffix = parse_hex(&value()),
+            "--merkle" => merkle = true,
+            "--chunk-size" => chunk_size = value().parse().unwrap_or_else(|_| usage()),
+            "--prove" => prove = Some(value().parse().unwrap_or_else(|_| usage())),
+            "--merkle-verify" => proof_path = Some(value()),
+            "-h" | "--help" => usage(),
+            "-" => paths.push(arg),
+            _ if arg.starts_with('-') => usage(),
+            _ => paths.push(arg),
+        }
+    }
+    if paths.is_empty() {
+        paths.push(String::from("-"));
+    }
+
+    if merkle || proof_path.is_some() {
+        let ([path], true) = (paths.as_slice(), chunk_size > 0) else {
+            usage();
+        };
+        let ok = match proof_path {
+            Some(proof_path) => verify_merkle_proof(&proof_path, path),
+            None => print_merkle_tree(path, chunk_size, prove),
+        };
+        if !ok {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(prefix) = mine_prefix {
+        if difficulty > 256 || threads == 0 {
+            usage();
+        }
+        let start = Instant::now();
+        let (solution, hashes) = miner::mine(&prefix, difficulty, threads);
+        let elapsed = start.elapsed().as_secs_f64();
+        eprintln!(
+            "{} hashes in {:.2} s on {} threads ({:.2} MH/s)",
+            hashes,
+            elapsed,
+            threads,
+            hashes as f64 / elapsed / 1e6,
+        );
+        match solution {
+            Some(solution) => println!("{}  {}", to_hex(&solution.nonce), to_hex(&solution.digest)),
+            None => {
+                eprintln!("no nonce reaches {} leading zero bits", difficulty);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(digest) = extend_digest {
+        let (Ok(digest), Some(len)) = (<[u8; 32]>::try_from(digest), original_length) else {
+            usage();
+        };
+        let (forged, extension) = length_extension::forge(&digest, len, &suffix);
+        println!("{}\n{}", to_hex(&forged), to_hex(&extension));
+        return;
+    }
+
+    if let Some(ikm) = hkdf_ikm {
+        let prk = hkdf::extract(&salt, &ikm);
+        let okm = hkdf::expand(&prk, &info, length).unwrap_or_else(|| {
+            eprintln!("HKDF output length {} exceeds {}", length, 255 * 32);
+            process::exit(1);
+        });
+        println!("{}", to_hex(&okm));
+        return;
+    }
+
+    if options.hmac_key.is_some() && !matches!(options.algorithm.as_str(), "sha256" | "sha512") {
+        eprintln!("--hmac needs -a sha256 or -a sha512");
+        usage();
+    }
+    if options.new_hasher().is_none() {
+        eprintln!("unknown algorithm {}", options.algorithm);
+        process::exit(1);
+    }
+
+    if let Some(tag) = expected_tag {
+        let (Some(_), [path]) = (&options.hmac_key, paths.as_slice()) else {
+            usage();
+        };
+        let mac = options.digest(path).unwrap_or_else(|err| {
+            eprintln!("code-1: {}: {}", path, err);
+            process::exit(1);
+        });
+        if !hmac::verify(&mac, &tag) {
+            eprintln!("HMAC verification failed");
+            process::exit(1);
+        }
+        return;
+    }
+
+    let ok = if check { check_lists(&options, &paths) } else { hash_files(&options, &paths) };
+    if !ok {
+        process::exit(1);
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("\
+usage: code-1 [-a <sha224|sha256|sha384|sha512|sha512-256>] [--tag] [--hmac <key hex>] [file...]
+       code-1 [-a <algorithm>] [--hmac <key hex>] -c [--quiet] [--status] [file...]
+       code-1 [-a <sha256|sha512>] --hmac <key hex> --verify <tag hex> [file]
+       code-1 --hkdf <ikm hex> [--salt <hex>] [--info <hex>] [--length <n>]
+       code-1 --mine <prefix hex> [--difficulty <bits>] [--threads <n>]
+       code-1 --extend <digest hex> --original-length <n> --append <hex>
+       code-1 --merkle [--chunk-size <n>] [--prove <index>] [file]
+       code-1 --merkle-verify <proof file> [chunk file]
+
+Prints `<digest>  <name>` per file like sha256sum, or `SHA256 (<name>) = <digest>` with
+--tag. With -c, each file is a list in either format whose entries are verified. A missing
+file or `-` reads stdin. --hmac replaces the digest with HMAC-SHA256, or HMAC-SHA512 with
+-a sha512; the other algorithms are not supported with --hmac.
+
+--mine searches for a 4-byte nonce A such that SHA-256(prefix || A) starts with the given
+number of zero bits (32 by default) and prints `<nonce>  <digest>`.
+
+--extend takes the SHA-256 digest of an unknown input of --original-length bytes and prints
+the digest of that input followed by its padding and the --append bytes, then the bytes to
+append (padding and suffix) on a second line.
+
+--merkle splits the input into --chunk-size byte leaves (4096 by default) and prints the
+RFC 6962 Merkle tree root. With --prove, a proof for that leaf follows: a line with the
+leaf index, tree size and leaf hash, then the audit path one hash per line. --merkle-verify
+checks such output (root line included) against the leaf's chunk.");
+    process::exit(1);
+}
+
+struct Options {
+    algorithm: String,
+    /// Replaces the plain digest with HMAC-SHA256 or HMAC-SHA512 under this key.
+    hmac_key: Option<Vec<u8>>,
+    tag: bool,
+    quiet: bool,
+    status: bool,
+}
+
+impl Options {
+    fn new_hasher(&self) -> Option<Hasher> {
+        match (&self.hmac_key, self.algorithm.as_str()) {
+            (Some(key), "sha256") => Some(Hasher::HmacSha256(hmac::HmacSha256::new(key))),
+            (Some(key), "sha512") => Some(Hasher::HmacSha512(Box::new(hmac::HmacSha512::new(key)))),
+            (Some(_), _) => None,
+            (None, algorithm) => Hasher::new(algorithm),
+        }
+    }
+
+    /// The algorithm name used by BSD-style tagged lines.
+    fn tag_name(&self) -> &'static str {
+        match self.algorithm.as_str() {
+            "sha256" if self.hmac_key.is_some() => "HMAC-SHA256",
+            "sha512" if self.hmac_key.is_some() => "HMAC-SHA512",
+            "sha224" => "SHA224",
+            "sha256" => "SHA256",
+            "sha384" => "SHA384",
+            "sha512" => "SHA512",
+            _ => "SHA512/256",
+        }
+    }
+
+    fn digest(&self, path: &str) -> io::Result<Vec<u8>> {
+        let mut file = open(path)?;
+        let mut ctx = self.new_hasher().unwrap();
+        let mut buf = vec![0u8; 640000];
+        loop {
+            let n = file.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            ctx.update(&buf[..n]);
+        }
+        Ok(ctx.finalize())
+    }
+}
+
+fn open(path: &str) -> io::Result<Box<dyn Read>> {
+    if path == "-" {
+        Ok(Box::new(io::stdin()))
+    } else {
+        Ok(Box::new(fs::File::open(path)?))
+    }
+}
+
+/// Files at most this large are read up front so that SHA-256 can hash them across SIMD lanes.
+const BATCH_LIMIT: u64 = 1 << 16;
+
+fn hash_files(options: &Options, paths: &[String]) -> bool {
+    let mut batched = vec![None; paths.len()];
+    if options.algorithm == "sha256" && options.hmac_key.is_none() {
+        let small = paths
+            .iter()
+            .enumerate()
+            .filter(|(_, path)| *path != "-" && fs::metadata(path).is_ok_and(|meta| meta.is_file() && meta.len() <= BATCH_LIMIT))
+            .filter_map(|(i, path)| fs::read(path).ok().map(|text| (i, text)))
+            .collect::<Vec<(usize, Vec<u8>)>>();
+        let messages = small.iter().map(|(_, text)| text.as_slice()).collect::<Vec<&[u8]>>();
+        for ((i, _), digest) in small.iter().zip(multi_buffer::digest_many(&messages)) {
+            batched[*i] = Some(digest.to_vec());
+        }
+    }
+
+    let mut ok = true;
+    let mut stdout = io::stdout().lock();
+    for (path, digest) in paths.iter().zip(batched) {
+        match digest.map_or_else(|| options.digest(path), Ok) {
+            Ok(digest) => writeln!(stdout, "{}", format_line(options, &to_hex(&digest), path)).unwrap(),
+            Err(err) => {
+                eprintln!("code-1: {}: {}", path, err);
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+/// Formats a checksum line the way GNU coreutils does, escaping backslashes and newlines in the
+/// name and flagging such lines with a leading backslash.
+fn format_line(options: &Options, digest: &str, name: &str) -> String {
+    let escaped = name.contains(['\\', '\n', '\r']);
+    let prefix = if escaped { "\\" } else { "" };
+    let name = if escaped {
+        name.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r")
+    } else {
+        name.to_string()
+    };
+
+    if options.tag {
+        format!("{}{} ({}) = {}", prefix, options.tag_name(), name, digest)
+    } else {
+        format!("{}{}  {}", prefix, digest, name)
+    }
+}
+
+/// Splits a checksum line in either the GNU (`<digest>  <name>`) or the BSD tagged format into
+/// its digest and name. Tagged lines must name the selected algorithm.
+fn parse_line(options: &Options, line: &str) -> Option<(Vec<u8>, String)> {
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+
+    let (digest, name) = match line.strip_prefix(options.tag_name()).and_then(|rest| rest.strip_prefix(" (")) {
+        Some(rest) => {
+            let (name, digest) = rest.rsplit_once(") = ")?;
+            (digest, name)
+        }
+        None => {
+            let (digest, rest) = line.split_once(' ')?;
+            (digest, rest.strip_prefix([' ', '*'])?)
+        }
+    };
+
+    let expected_len = options.new_hasher()?.output_len();
+    if digest.len() != expected_len * 2 || !digest.bytes().all(|b| b.is_ascii_hexdigit()) || name.is_empty() {
+        return None;
+    }
+    let digest = (0..digest.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digest[i..i + 2], 16).unwrap())
+        .collect();
+
+    let name = if escaped {
+        let mut res = String::new();
+        let mut chars = name.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                res.push(c);
+                continue;
+            }
+            match chars.next()? {
+                '\\' => res.push('\\'),
+                'n' => res.push('\n'),
+                'r' => res.push('\r'),
+                _ => return None,
+            }
+        }
+        res
+    } else {
+        name.to_string()
+    };
+    Some((digest, name))
+}
+
+fn check_lists(options: &Options, lists: &[String]) -> bool {
+    let mut ok = true;
+    for list in lists {
+        let mut text = String::new();
+        if let Err(err) = open(list).and_then(|mut file| file.read_to_string(&mut text)) {
+            eprintln!("code-1: {}: {}", list, err);
+            ok = false;
+            continue;
+        }
+
+        let (mut properly_formatted, mut improperly_formatted, mut unreadable, mut mismatched) = (0, 0, 0, 0);
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let Some((expected, name)) = parse_line(options, line.trim_end_matches('\r')) else {
+                improperly_formatted += 1;
+                continue;
+            };
+            properly_formatted += 1;
+
+            let status = match options.digest(&name) {
+                Ok(digest) if hmac::ct_eq(&digest, &expected) => "OK",
+                Ok(_) => {
+                    mismatched += 1;
+                    "FAILED"
+                }
+                Err(err) => {
+                    eprintln!("code-1: {}: {}", name, err);
+                    unreadable += 1;
+                    "FAILED open or read"
+                }
+            };
+            let silent = options.status || (options.quiet && status == "OK");
+            if !silent {
+                println!("{}: {}", name, status);
+            }
+        }
+
+        if properly_formatted == 0 {
+            eprintln!("code-1: {}: no properly formatted checksum lines found", list);
+            ok = false;
+            continue;
+        }
+        if !options.status {
+            warn(improperly_formatted, "line is improperly formatted", "lines are improperly formatted");
+            warn(unreadable, "listed file could not be read", "listed files could not be read");
+            warn(mismatched, "computed checksum did NOT match", "computed checksums did NOT match");
+        }
+        ok &= unreadable == 0 && mismatched == 0;
+    }
+    ok
+}
+
+fn warn(count: usize, singular: &str, plural: &str) {
+    match count {
+        0 => {}
+        1 => eprintln!("code-1: WARNING: 1 {}", singular),
+        _ => eprintln!("code-1: WARNING: {} {}", count, plural),
+    }
+}
+
+/// Streams `path` into a Merkle tree one chunk at a time and prints its root, followed by the
+/// proof for leaf `prove` when requested.
+fn print_merkle_tree(path: &str, chunk_size: usize, prove: Option<u64>) -> bool {
+    let mut builder = merkle::Builder::new(prove);
+    let mut leaf = None;
+    let result = open(path).and_then(|mut file| {
+        let mut buf = vec![0u8; chunk_size];
+        let mut index = 0;
+        loop {
+            let mut n = 0;
+            while n < chunk_size {
+                match file.read(&mut buf[n..])? {
+                    0 => break,
+                    read => n += read,
+                }
+            }
+            if n == 0 {
+                return Ok(());
+            }
+            builder.push(&buf[..n]);
+            if prove == Some(index) {
+                leaf = Some(merkle::leaf_hash(&buf[..n]));
+            }
+            index += 1;
+        }
+    });
+    if let Err(err) = result {
+        eprintln!("code-1: {}: {}", path, err);
+        return false;
+    }
+
+    let (size, root, audit_path) = builder.finish();
+    println!("{}  {}", to_hex(&root), path);
+    if let Some(index) = prove {
+        let Some(leaf) = leaf else {
+            eprintln!("code-1: leaf {} is out of range for a tree of {} leaves", index, size);
+            return false;
+        };
+        println!("{} {} {}", index, size, to_hex(&leaf));
+        audit_path.iter().for_each(|hash| println!("{}", to_hex(hash)));
+    }
+    true
+}
+
+/// Checks that the chunk read from `path` is the proven leaf of the root in `proof_path`.
+fn verify_merkle_proof(proof_path: &str, path: &str) -> bool {
+    let parse_hash = |text: &str| <[u8; 32]>::try_from(parse_hex(text)).ok();
+    let mut proof = String::new();
+    let mut chunk = Vec::new();
+    if let Err(err) = fs::File::open(proof_path).and_then(|mut file| file.read_to_string(&mut proof)) {
+        eprintln!("code-1: {}: {}", proof_path, err);
+        return false;
+    }
+    if let Err(err) = open(path).and_then(|mut file| file.read_to_end(&mut chunk)) {
+        eprintln!("code-1: {}: {}", path, err);
+        return false;
+    }
+
+    let mut lines = proof.lines();
+    let root = lines.next().and_then(|line| parse_hash(line.split_whitespace().next()?));
+    let header = lines.next().map(|line| line.split_whitespace().collect::<Vec<&str>>());
+    let audit_path = lines.map(parse_hash).collect::<Option<Vec<[u8; 32]>>>();
+    let (Some(root), Some(&[index, size, leaf]), Some(audit_path)) = (root, header.as_deref(), audit_path) else {
+        eprintln!("code-1: {}: malformed proof", proof_path);
+        return false;
+    };
+    let (Ok(index), Ok(size), Some(leaf)) = (index.parse(), size.parse(), parse_hash(leaf)) else {
+        eprintln!("code-1: {}: malformed proof", proof_path);
+        return false;
+    };
+
+    let ok = merkle::leaf_hash(&chunk) == leaf && merkle::verify(index, size, &leaf, &audit_path, &root);
+    println!("{}: {}", path, if ok { "OK" } else { "FAILED" });
+    ok
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn parse_hex(text: &str) -> Vec<u8> {
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        eprintln!("invalid hex string `{}`", text);
+        process::exit(1);
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// A SHA-2 context chosen at runtime. SHA-224 and SHA-384/SHA-512/256 run the SHA-256 and SHA-512
+/// compression functions from their own initial values and truncate the output.
+enum Hasher {
+    Sha256(SHA256, usize),
+    Sha512(SHA512, usize),
+    HmacSha256(hmac::HmacSha256),
+    HmacSha512(Box<hmac::HmacSha512>),
+}
+
+impl Hasher {
+    fn new(algorithm: &str) -> Option<Self> {
+        match algorithm {
+            "sha224" => Some(Hasher::Sha256(SHA256::with_iv(H224), 28)),
+            "sha256" => Some(Hasher::Sha256(SHA256::new(), 32)),
+            "sha384" => Some(Hasher::Sha512(SHA512::with_iv(H384), 48)),
+            "sha512" => Some(Hasher::Sha512(SHA512::new(), 64)),
+            "sha512-256" => Some(Hasher::Sha512(SHA512::with_iv(H512_256), 32)),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, text: &[u8]) {
+        match self {
+            Hasher::Sha256(ctx, _) => ctx.update(text),
+            Hasher::Sha512(ctx, _) => ctx.update(text),
+            Hasher::HmacSha256(ctx) => ctx.update(text),
+            Hasher::HmacSha512(ctx) => ctx.update(text),
+        }
+    }
+
+    fn finalize(&mut self) -> Vec<u8> {
+        match self {
+            Hasher::Sha256(ctx, out_len) => ctx.finalize()[..*out_len].to_vec(),
+            Hasher::Sha512(ctx, out_len) => ctx.finalize()[..*out_len].to_vec(),
+            Hasher::HmacSha256(ctx) => ctx.finalize().to_vec(),
+            Hasher::HmacSha512(ctx) => ctx.finalize().to_vec(),
+        }
+    }
+
+    fn output_len(&self) -> usize {
+        match self {
+            Hasher::Sha256(_, out_len) | Hasher::Sha512(_, out_len) => *out_len,
+            Hasher::HmacSha256(_) => 32,
+            Hasher::HmacSha512(_) => 64,
         }
-        ctx.update(&buf[..n]);
     }
-    io::stdout().write_all(&ctx.finalize()).unwrap();
 }
 
+/// SHA-256 (FIPS 180-4). lab/03/code-1 keeps a trimmed copy of the portable path for PBKDF2,
+/// so changes to `update`, `finalize` or `process_chunk` should be mirrored there.
+#[derive(Clone)]
 struct SHA256 {
     buffer: [u8; 64],
     buffer_len: usize,
@@ -24,26 +560,62 @@ struct SHA256 {
 
 impl SHA256 {
     fn new() -> Self {
+        SHA256::with_iv(H)
+    }
+
+    fn with_iv(h: [u32; 8]) -> Self {
         SHA256 {
             buffer: [0u8; 64],
             buffer_len: 0,
-            h: H,
+            h,
             len: 0,
             use_sha_ni: detect_sha_ni(),
         }
     }
 
-    fn update(&mut self, text: &[u8]) {
+    /// Resumes hashing from a finished digest, as if `len` bytes (a multiple of 64, padding
+    /// included) had already been absorbed. The digest is the full chaining state.
+    fn from_digest(digest: &[u8; 32], len: usize) -> Self {
+        let mut h = [0u32; 8];
+        digest.chunks(4).enumerate().for_each(|(i, bytes)| {
+            h[i] = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        });
+        let mut ctx = SHA256::with_iv(h);
+        ctx.len = len;
+        ctx
+    }
+
+    /// The bytes `finalize` appends to a message of `len` bytes: 0x80, zeros up to 56 mod 64,
+    /// then the bit length.
+    fn padding(len: usize) -> Vec<u8> {
+        let mut res = vec![0x80];
+        res.resize((len + 9).div_ceil(64) * 64 - len - 8, 0);
+        res.extend_from_slice(&(len as u64 * 8).to_be_bytes());
+        res
+    }
+
+    fn update(&mut self, mut text: &[u8]) {
         self.len += text.len();
-        text.chunks(64)
-            .for_each(|chunk| {
-                if chunk.len() < 64 {
-                    self.buffer_len = chunk.len();
-                    self.buffer[..self.buffer_len].copy_from_slice(chunk);
-                    return;
-                }
-                self.process_chunk(chunk);
-            });
+
+        // Top up a partial chunk left by the previous call before hashing whole chunks.
+        if self.buffer_len > 0 {
+            let take = text.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&text[..take]);
+            self.buffer_len += take;
+            text = &text[take..];
+            if self.buffer_len < 64 {
+                return;
+            }
+            let temp = self.buffer;
+            self.process_chunk(&temp);
+            self.buffer_len = 0;
+        }
+
+        let mut chunks = text.chunks_exact(64);
+        chunks.by_ref().for_each(|chunk| self.process_chunk(chunk));
+        let rest = chunks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
     }
 
     fn finalize(&mut self) -> [u8; 32] {
@@ -132,29 +704,9 @@ impl SHA256 {
 
     fn try_process_chunk_sha_ni(&mut self, chunk: &[u8]) -> bool {
         #[cfg(target_arch = "x86_64")]
-        unsafe {
-            use std::arch::x86_64::*;
-
-            let mut w = [0i32; 16];
-            for (i, bytes) in chunk.chunks(4).enumerate() {
-                w[i] = i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
-            }
-
-            let zero = _mm_setzero_si128();
-            let mut w128 = [zero; 16];
-            for (i, words) in w.chunks(4).enumerate() {
-                w128[i] = _mm_set_epi32(words[3], words[2], words[1], words[0]);
-            }
-
-            for i in 4..16 {
-                let x = {
-                    let y = _mm_srli_si128(w128[i - 2], 4);
-                    _mm_insert_epi32(y, _mm_cvtsi128_si32(w128[i - 1]), 3);
-                    _mm_add_epi32(_mm_sha256msg1_epu32(w128[i - 4], w128[i - 3]), y)
-                };
-                w128[i] = _mm_sha256msg2_epu32(x, w128[i - 1]);
-            }
-
+        {
+            // Safety: `use_sha_ni` is only set when the required CPU features were detected.
+            unsafe { sha_ni::process_chunk(&mut self.h, chunk) };
             true
         }
 
@@ -166,6 +718,127 @@ impl SHA256 {
     }
 }
 
+struct SHA512 {
+    buffer: [u8; 128],
+    buffer_len: usize,
+    h: [u64; 8],
+    len: u128,
+}
+
+impl SHA512 {
+    fn new() -> Self {
+        SHA512::with_iv(H512)
+    }
+
+    fn with_iv(h: [u64; 8]) -> Self {
+        SHA512 {
+            buffer: [0u8; 128],
+            buffer_len: 0,
+            h,
+            len: 0,
+        }
+    }
+
+    fn update(&mut self, mut text: &[u8]) {
+        self.len += text.len() as u128;
+
+        if self.buffer_len > 0 {
+            let take = text.len().min(128 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&text[..take]);
+            self.buffer_len += take;
+            text = &text[take..];
+            if self.buffer_len < 128 {
+                return;
+            }
+            let temp = self.buffer;
+            self.process_chunk(&temp);
+            self.buffer_len = 0;
+        }
+
+        let mut chunks = text.chunks_exact(128);
+        chunks.by_ref().for_each(|chunk| self.process_chunk(chunk));
+        let rest = chunks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+    }
+
+    fn finalize(&mut self) -> [u8; 64] {
+        self.buffer[self.buffer_len] = 0x80;
+        self.buffer_len += 1;
+
+        if self.buffer_len > 112 {
+            self.buffer[self.buffer_len..].fill(0);
+            let temp = self.buffer;
+            self.process_chunk(&temp);
+            self.buffer_len = 0;
+        }
+
+        self.buffer[self.buffer_len..112].fill(0);
+
+        let len = self.len * 8;
+        self.buffer[112..128].copy_from_slice(&len.to_be_bytes());
+        let temp = self.buffer;
+        self.process_chunk(&temp);
+
+        let mut res = [0u8; 64];
+        self.h.iter().enumerate().for_each(|(i, &word)| {
+            res[i * 8..(i + 1) * 8].copy_from_slice(&word.to_be_bytes());
+        });
+        res
+    }
+
+    fn process_chunk(&mut self, chunk: &[u8]) {
+        let mut w = [0u64; 80];
+        chunk.chunks(8).enumerate().for_each(|(i, bytes)| {
+            w[i] = u64::from_be_bytes(bytes.try_into().unwrap());
+        });
+
+        for i in 16..80 {
+            w[i] = w[i - 16]
+                .wrapping_add(small_sigma0_512(w[i - 15]))
+                .wrapping_add(w[i - 7])
+                .wrapping_add(small_sigma1_512(w[i - 2]));
+        }
+
+        let mut a = self.h[0];
+        let mut b = self.h[1];
+        let mut c = self.h[2];
+        let mut d = self.h[3];
+        let mut e = self.h[4];
+        let mut f = self.h[5];
+        let mut g = self.h[6];
+        let mut h = self.h[7];
+
+        for i in 0..80 {
+            let temp1 = h
+                .wrapping_add(big_sigma1_512(e))
+                .wrapping_add((e & f) ^ (!e & g))
+                .wrapping_add(K512[i])
+                .wrapping_add(w[i]);
+            let temp2 = big_sigma0_512(a)
+                .wrapping_add((a & b) ^ (a & c) ^ (b & c));
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+
+        self.h[0] = self.h[0].wrapping_add(a);
+        self.h[1] = self.h[1].wrapping_add(b);
+        self.h[2] = self.h[2].wrapping_add(c);
+        self.h[3] = self.h[3].wrapping_add(d);
+        self.h[4] = self.h[4].wrapping_add(e);
+        self.h[5] = self.h[5].wrapping_add(f);
+        self.h[6] = self.h[6].wrapping_add(g);
+        self.h[7] = self.h[7].wrapping_add(h);
+    }
+}
+
 #[inline(always)]
 fn small_sigma0(x: u32) -> u32 {
     x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3)
@@ -196,18 +869,633 @@ fn majority(x: u32, y: u32, z: u32) -> u32 {
     (x & y) ^ (x & z) ^ (y & z)
 }
 
+#[inline(always)]
+fn small_sigma0_512(x: u64) -> u64 {
+    x.rotate_right(1) ^ x.rotate_right(8) ^ (x >> 7)
+}
+
+#[inline(always)]
+fn small_sigma1_512(x: u64) -> u64 {
+    x.rotate_right(19) ^ x.rotate_right(61) ^ (x >> 6)
+}
+
+#[inline(always)]
+fn big_sigma0_512(x: u64) -> u64 {
+    x.rotate_right(28) ^ x.rotate_right(34) ^ x.rotate_right(39)
+}
+
+#[inline(always)]
+fn big_sigma1_512(x: u64) -> u64 {
+    x.rotate_right(14) ^ x.rotate_right(18) ^ x.rotate_right(41)
+}
+
 fn detect_sha_ni() -> bool {
-    #[cfg(target_arch = "x86_64")]
+    #[cfg(all(target_arch = "x86_64", not(feature = "force_soft")))]
     {
-        // std::is_x86_feature_detected!("sha")
-        false
+        std::is_x86_feature_detected!("sha")
+            && std::is_x86_feature_detected!("ssse3")
+            && std::is_x86_feature_detected!("sse4.1")
     }
-    #[cfg(not(target_arch = "x86_64"))]
+    #[cfg(not(all(target_arch = "x86_64", not(feature = "force_soft"))))]
     {
         false
     }
 }
 
+#[cfg(target_arch = "x86_64")]
+mod sha_ni {
+    use std::arch::x86_64::*;
+
+    /// Compresses one 64-byte chunk into `h` with the SHA extensions.
+    ///
+    /// `sha256rnds2` keeps the state as the two vectors ABEF and CDGH and performs two rounds per
+    /// call, taking the pre-added `W + K` words from the low half of its third operand.
+    #[target_feature(enable = "sha,ssse3,sse4.1")]
+    pub unsafe fn process_chunk(h: &mut [u32; 8], chunk: &[u8]) {
+        unsafe {
+            let byte_swap = _mm_set_epi64x(0x0c0d0e0f08090a0b, 0x0405060700010203);
+
+            let dcba = _mm_loadu_si128(h.as_ptr() as *const __m128i);
+            let hgfe = _mm_loadu_si128(h.as_ptr().add(4) as *const __m128i);
+            let cdab = _mm_shuffle_epi32(dcba, 0xb1);
+            let efgh = _mm_shuffle_epi32(hgfe, 0x1b);
+            let mut abef = _mm_alignr_epi8(cdab, efgh, 8);
+            let mut cdgh = _mm_blend_epi16(efgh, cdab, 0xf0);
+            let (abef_save, cdgh_save) = (abef, cdgh);
+
+            let mut w = [_mm_setzero_si128(); 16];
+            for (i, bytes) in chunk.chunks(16).enumerate() {
+                w[i] = _mm_shuffle_epi8(_mm_loadu_si128(bytes.as_ptr() as *const __m128i), byte_swap);
+            }
+            for i in 4..16 {
+                let x = _mm_add_epi32(
+                    _mm_sha256msg1_epu32(w[i - 4], w[i - 3]),
+                    _mm_alignr_epi8(w[i - 1], w[i - 2], 4),
+                );
+                w[i] = _mm_sha256msg2_epu32(x, w[i - 1]);
+            }
+
+            for (i, words) in w.iter().enumerate() {
+                let k = _mm_loadu_si128(crate::K.as_ptr().add(i * 4) as *const __m128i);
+                let wk = _mm_add_epi32(*words, k);
+                cdgh = _mm_sha256rnds2_epu32(cdgh, abef, wk);
+                abef = _mm_sha256rnds2_epu32(abef, cdgh, _mm_shuffle_epi32(wk, 0x0e));
+            }
+
+            abef = _mm_add_epi32(abef, abef_save);
+            cdgh = _mm_add_epi32(cdgh, cdgh_save);
+
+            let feba = _mm_shuffle_epi32(abef, 0x1b);
+            let dchg = _mm_shuffle_epi32(cdgh, 0xb1);
+            let dcba = _mm_blend_epi16(feba, dchg, 0xf0);
+            let hgfe = _mm_alignr_epi8(dchg, feba, 8);
+            _mm_storeu_si128(h.as_mut_ptr() as *mut __m128i, dcba);
+            _mm_storeu_si128(h.as_mut_ptr().add(4) as *mut __m128i, hgfe);
+        }
+    }
+}
+
+/// Multi-buffer SHA-256: independent messages are hashed side by side, one message per 32-bit
+/// SIMD lane (8 with AVX2, 4 with SSE2), each lane running the same compression as `process_chunk`.
+mod multi_buffer {
+    use crate::SHA256;
+
+    pub fn digest_many(messages: &[&[u8]]) -> Vec<[u8; 32]> {
+        let lanes = lane_count();
+        if lanes == 1 {
+            return messages.iter().map(|text| {
+                let mut ctx = SHA256::new();
+                ctx.update(text);
+                ctx.finalize()
+            }).collect();
+        }
+
+        // Sorting by length keeps lanes of a group finishing at about the same block.
+        let mut order = (0..messages.len()).collect::<Vec<usize>>();
+        order.sort_by_key(|&i| messages[i].len());
+
+        let mut res = vec![[0u8; 32]; messages.len()];
+        for group in order.chunks(lanes) {
+            let padded = group.iter().map(|&i| pad(messages[i])).collect::<Vec<Vec<u8>>>();
+            for (&i, h) in group.iter().zip(hash_group(&padded)) {
+                h.iter().enumerate().for_each(|(j, &word)| {
+                    res[i][j * 4..(j + 1) * 4].copy_from_slice(&word.to_be_bytes());
+                });
+            }
+        }
+        res
+    }
+
+    /// Applies the same padding as `SHA256::finalize`: 0x80, zeros, then the bit length.
+    fn pad(text: &[u8]) -> Vec<u8> {
+        let mut res = text.to_vec();
+        res.push(0x80);
+        res.resize((text.len() + 9).div_ceil(64) * 64, 0);
+        let len = res.len();
+        res[len - 8..].copy_from_slice(&(text.len() as u64 * 8).to_be_bytes());
+        res
+    }
+
+    fn lane_count() -> usize {
+        #[cfg(all(target_arch = "x86_64", not(feature = "force_soft")))]
+        {
+            if std::is_x86_feature_detected!("avx2") { 8 } else { 4 }
+        }
+        #[cfg(not(all(target_arch = "x86_64", not(feature = "force_soft"))))]
+        {
+            1
+        }
+    }
+
+    fn hash_group(padded: &[Vec<u8>]) -> Vec<[u32; 8]> {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if padded.len() > sse2::LANES {
+                // Safety: groups wider than four lanes are only formed once AVX2 was detected.
+                unsafe { avx2::hash(padded) }
+            } else {
+                // Safety: SSE2 is part of the x86_64 baseline.
+                unsafe { sse2::hash(padded) }
+            }
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        {
+            let _ = padded;
+            unreachable!()
+        }
+    }
+
+    /// Generates a lane module from the 32-bit integer intrinsics of one vector width.
+    macro_rules! lanes {
+        (
+            $module:ident, $feature:literal, $lanes:literal, $vec:ident,
+            $set1:ident, $loadu:ident, $storeu:ident, $add:ident,
+            $and:ident, $andnot:ident, $or:ident, $xor:ident, $slli:ident, $srli:ident
+        ) => {
+            #[cfg(target_arch = "x86_64")]
+            mod $module {
+                use std::arch::x86_64::*;
+
+                pub const LANES: usize = $lanes;
+
+                #[inline]
+                #[target_feature(enable = $feature)]
+                fn rotr<const R: i32, const L: i32>(x: $vec) -> $vec {
+                    $or($srli::<R>(x), $slli::<L>(x))
+                }
+
+                #[inline]
+                #[target_feature(enable = $feature)]
+                fn small_sigma0(x: $vec) -> $vec {
+                    $xor($xor(rotr::<7, 25>(x), rotr::<18, 14>(x)), $srli::<3>(x))
+                }
+
+                #[inline]
+                #[target_feature(enable = $feature)]
+                fn small_sigma1(x: $vec) -> $vec {
+                    $xor($xor(rotr::<17, 15>(x), rotr::<19, 13>(x)), $srli::<10>(x))
+                }
+
+                #[inline]
+                #[target_feature(enable = $feature)]
+                fn big_sigma0(x: $vec) -> $vec {
+                    $xor($xor(rotr::<2, 30>(x), rotr::<13, 19>(x)), rotr::<22, 10>(x))
+                }
+
+                #[inline]
+                #[target_feature(enable = $feature)]
+                fn big_sigma1(x: $vec) -> $vec {
+                    $xor($xor(rotr::<6, 26>(x), rotr::<11, 21>(x)), rotr::<25, 7>(x))
+                }
+
+                /// Loads one 32-bit word per lane; lanes past `words.len()` are zero.
+                #[inline]
+                #[target_feature(enable = $feature)]
+                fn gather(words: impl Iterator<Item = u32>) -> $vec {
+                    let mut buf = [0u32; LANES];
+                    buf.iter_mut().zip(words).for_each(|(slot, word)| *slot = word);
+                    unsafe { $loadu(buf.as_ptr() as *const $vec) }
+                }
+
+                /// Runs up to `LANES` padded messages through the compression function in lockstep.
+                /// A lane whose message has no blocks left is fed zeros and keeps its state.
+                #[target_feature(enable = $feature)]
+                pub fn hash(padded: &[Vec<u8>]) -> Vec<[u32; 8]> {
+                    let blocks = padded.iter().map(|text| text.len() / 64).collect::<Vec<usize>>();
+                    let mut state = crate::H.map(|word| $set1(word as i32));
+
+                    for block in 0..blocks.iter().copied().max().unwrap_or(0) {
+                        let active = gather(blocks.iter().map(|&n| if block < n { u32::MAX } else { 0 }));
+
+                        let mut w = [$set1(0); 64];
+                        for (t, word) in w.iter_mut().take(16).enumerate() {
+                            *word = gather(padded.iter().zip(&blocks).map(|(text, &n)| {
+                                if block < n {
+                                    let at = block * 64 + t * 4;
+                                    u32::from_be_bytes(text[at..at + 4].try_into().unwrap())
+                                } else {
+                                    0
+                                }
+                            }));
+                        }
+                        for i in 16..64 {
+                            w[i] = $add(
+                                $add(w[i - 16], small_sigma0(w[i - 15])),
+                                $add(w[i - 7], small_sigma1(w[i - 2])),
+                            );
+                        }
+
+                        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+                        for i in 0..64 {
+                            let choice = $xor($and(e, f), $andnot(e, g));
+                            let majority = $xor($xor($and(a, b), $and(a, c)), $and(b, c));
+                            let temp1 = $add(
+                                $add(h, big_sigma1(e)),
+                                $add($add(choice, $set1(crate::K[i] as i32)), w[i]),
+                            );
+                            let temp2 = $add(big_sigma0(a), majority);
+
+                            h = g;
+                            g = f;
+                            f = e;
+                            e = $add(d, temp1);
+                            d = c;
+                            c = b;
+                            b = a;
+                            a = $add(temp1, temp2);
+                        }
+
+                        for (word, new) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+                            *word = $or($and(active, $add(*word, new)), $andnot(active, *word));
+                        }
+                    }
+
+                    let mut res = vec![[0u32; 8]; padded.len()];
+                    for (j, word) in state.iter().enumerate() {
+                        let mut buf = [0u32; LANES];
+                        unsafe { $storeu(buf.as_mut_ptr() as *mut $vec, *word) };
+                        res.iter_mut().zip(buf).for_each(|(h, lane)| h[j] = lane);
+                    }
+                    res
+                }
+            }
+        };
+    }
+
+    lanes!(
+        sse2, "sse2", 4, __m128i,
+        _mm_set1_epi32, _mm_loadu_si128, _mm_storeu_si128, _mm_add_epi32,
+        _mm_and_si128, _mm_andnot_si128, _mm_or_si128, _mm_xor_si128, _mm_slli_epi32, _mm_srli_epi32
+    );
+    lanes!(
+        avx2, "avx2", 8, __m256i,
+        _mm256_set1_epi32, _mm256_loadu_si256, _mm256_storeu_si256, _mm256_add_epi32,
+        _mm256_and_si256, _mm256_andnot_si256, _mm256_or_si256, _mm256_xor_si256, _mm256_slli_epi32, _mm256_srli_epi32
+    );
+}
+
+/// HMAC-SHA256 and HMAC-SHA512 (RFC 2104).
+mod hmac {
+    use crate::{SHA256, SHA512};
+
+    /// A keyed context; the padded key is absorbed into both halves up front.
+    #[derive(Clone)]
+    pub struct HmacSha256 {
+        inner: SHA256,
+        outer: SHA256,
+    }
+
+    impl HmacSha256 {
+        pub fn new(key: &[u8]) -> Self {
+            let mut block = [0u8; 64];
+            if key.len() > 64 {
+                let mut ctx = SHA256::new();
+                ctx.update(key);
+                block[..32].copy_from_slice(&ctx.finalize());
+            } else {
+                block[..key.len()].copy_from_slice(key);
+            }
+
+            let mut inner = SHA256::new();
+            inner.update(&block.map(|b| b ^ 0x36));
+            let mut outer = SHA256::new();
+            outer.update(&block.map(|b| b ^ 0x5c));
+            HmacSha256 { inner, outer }
+        }
+
+        pub fn update(&mut self, text: &[u8]) {
+            self.inner.update(text);
+        }
+
+        pub fn finalize(&mut self) -> [u8; 32] {
+            let digest = self.inner.finalize();
+            self.outer.update(&digest);
+            self.outer.finalize()
+        }
+    }
+
+    /// The same construction over SHA-512, whose block is 128 bytes.
+    pub struct HmacSha512 {
+        inner: SHA512,
+        outer: SHA512,
+    }
+
+    impl HmacSha512 {
+        pub fn new(key: &[u8]) -> Self {
+            let mut block = [0u8; 128];
+            if key.len() > 128 {
+                let mut ctx = SHA512::new();
+                ctx.update(key);
+                block[..64].copy_from_slice(&ctx.finalize());
+            } else {
+                block[..key.len()].copy_from_slice(key);
+            }
+
+            let mut inner = SHA512::new();
+            inner.update(&block.map(|b| b ^ 0x36));
+            let mut outer = SHA512::new();
+            outer.update(&block.map(|b| b ^ 0x5c));
+            HmacSha512 { inner, outer }
+        }
+
+        pub fn update(&mut self, text: &[u8]) {
+            self.inner.update(text);
+        }
+
+        pub fn finalize(&mut self) -> [u8; 64] {
+            let digest = self.inner.finalize();
+            self.outer.update(&digest);
+            self.outer.finalize()
+        }
+    }
+
+    pub fn hmac_sha256(key: &[u8], text: &[u8]) -> [u8; 32] {
+        let mut ctx = HmacSha256::new(key);
+        ctx.update(text);
+        ctx.finalize()
+    }
+
+    /// Compares `tag`, which may be truncated to at least 16 bytes, against the full `mac`
+    /// without branching on the position of the first difference.
+    pub fn verify(mac: &[u8], tag: &[u8]) -> bool {
+        if tag.len() < 16 || tag.len() > mac.len() {
+            return false;
+        }
+        ct_eq(&mac[..tag.len()], tag)
+    }
+
+    /// Equality of two byte strings whose running time depends only on their lengths.
+    pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+    }
+}
+
+/// HKDF with HMAC-SHA256 (RFC 5869).
+mod hkdf {
+    use crate::hmac::{HmacSha256, hmac_sha256};
+
+    /// An absent salt is treated as 32 zero bytes, which HMAC pads to the same key as an empty one.
+    pub fn extract(salt: &[u8], ikm: &[u8]) -> [u8; 32] {
+        hmac_sha256(salt, ikm)
+    }
+
+    /// Returns `None` when `len` exceeds the 255-block limit of the expand step.
+    pub fn expand(prk: &[u8], info: &[u8], len: usize) -> Option<Vec<u8>> {
+        if len > 255 * 32 {
+            return None;
+        }
+
+        let base = HmacSha256::new(prk);
+        let mut res = Vec::with_capacity(len);
+        let mut t = [0u8; 32];
+        for i in 1..=len.div_ceil(32) as u8 {
+            let mut ctx = base.clone();
+            if i > 1 {
+                ctx.update(&t);
+            }
+            ctx.update(info);
+            ctx.update(&[i]);
+            t = ctx.finalize();
+            res.extend_from_slice(&t);
+        }
+        res.truncate(len);
+        Some(res)
+    }
+}
+
+/// Proof-of-work search in the style of glminer: find a 4-byte nonce A such that SHA-256(Q || A)
+/// starts with a given number of zero bits.
+mod miner {
+    use crate::SHA256;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::thread;
+
+    /// Nonces are handed out to threads in batches of this size.
+    const BATCH: u64 = 1 << 16;
+
+    pub struct Solution {
+        pub nonce: [u8; 4],
+        pub digest: [u8; 32],
+    }
+
+    /// Searches the whole 32-bit nonce space, returning a solution if one exists together with the
+    /// number of hashes computed.
+    pub fn mine(prefix: &[u8], difficulty: u32, threads: usize) -> (Option<Solution>, u64) {
+        // Every candidate shares the prefix, so the chunks it fills are compressed once here and
+        // each attempt only clones this midstate and finalizes the last one or two chunks.
+        let mut midstate = SHA256::new();
+        midstate.update(prefix);
+
+        let next = AtomicU64::new(0);
+        let hashes = AtomicU64::new(0);
+        let found = AtomicBool::new(false);
+
+        let solution = thread::scope(|scope| {
+            let workers = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        while !found.load(Ordering::Relaxed) {
+                            let start = next.fetch_add(BATCH, Ordering::Relaxed);
+                            if start > u32::MAX as u64 {
+                                return None;
+                            }
+                            for nonce in start..start + BATCH {
+                                let nonce = (nonce as u32).to_be_bytes();
+                                let mut ctx = midstate.clone();
+                                ctx.update(&nonce);
+                                let digest = ctx.finalize();
+                                if leading_zero_bits(&digest) >= difficulty {
+                                    found.store(true, Ordering::Relaxed);
+                                    hashes.fetch_add(nonce_count(start, &nonce), Ordering::Relaxed);
+                                    return Some(Solution { nonce, digest });
+                                }
+                            }
+                            hashes.fetch_add(BATCH, Ordering::Relaxed);
+                        }
+                        None
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers.into_iter().filter_map(|worker| worker.join().unwrap()).next()
+        });
+
+        (solution, hashes.into_inner())
+    }
+
+    /// The number of nonces tried in the batch starting at `start`, up to and including `nonce`.
+    fn nonce_count(start: u64, nonce: &[u8; 4]) -> u64 {
+        u32::from_be_bytes(*nonce) as u64 - start + 1
+    }
+
+    pub fn leading_zero_bits(digest: &[u8; 32]) -> u32 {
+        let mut bits = 0;
+        for &byte in digest {
+            bits += byte.leading_zeros();
+            if byte != 0 {
+                break;
+            }
+        }
+        bits
+    }
+}
+
+/// SHA-256 length extension: the digest of `secret || message` is the whole hash state, so hashing
+/// can resume from it to produce the digest of `secret || message || padding || suffix`.
+mod length_extension {
+    use crate::SHA256;
+
+    /// Returns the forged digest and the bytes to append to the message, given the digest of the
+    /// original input and its length (secret included).
+    pub fn forge(digest: &[u8; 32], len: usize, suffix: &[u8]) -> ([u8; 32], Vec<u8>) {
+        let mut extension = SHA256::padding(len);
+        let mut ctx = SHA256::from_digest(digest, len + extension.len());
+        ctx.update(suffix);
+        extension.extend_from_slice(suffix);
+        (ctx.finalize(), extension)
+    }
+}
+
+/// Merkle tree hashing as in RFC 6962 (Certificate Transparency), with 0x00 and 0x01 prefixes
+/// separating leaf hashes from interior node hashes.
+mod merkle {
+    use crate::SHA256;
+
+    pub fn leaf_hash(data: &[u8]) -> [u8; 32] {
+        let mut ctx = SHA256::new();
+        ctx.update(&[0x00]);
+        ctx.update(data);
+        ctx.finalize()
+    }
+
+    pub fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut ctx = SHA256::new();
+        ctx.update(&[0x01]);
+        ctx.update(left);
+        ctx.update(right);
+        ctx.finalize()
+    }
+
+    /// A complete subtree over the leaves `start..start + size`, where `size` is a power of two.
+    struct Subtree {
+        start: u64,
+        size: u64,
+        hash: [u8; 32],
+    }
+
+    /// Builds the tree from leaves pushed one at a time, keeping only the roots of the complete
+    /// subtrees seen so far (one per set bit of the leaf count) instead of the leaves.
+    pub struct Builder {
+        stack: Vec<Subtree>,
+        count: u64,
+        /// The leaf whose audit path is collected while building, if any.
+        target: Option<u64>,
+        path: Vec<[u8; 32]>,
+    }
+
+    impl Builder {
+        pub fn new(target: Option<u64>) -> Self {
+            Builder {
+                stack: Vec::new(),
+                count: 0,
+                target,
+                path: Vec::new(),
+            }
+        }
+
+        pub fn push(&mut self, data: &[u8]) {
+            self.stack.push(Subtree {
+                start: self.count,
+                size: 1,
+                hash: leaf_hash(data),
+            });
+            self.count += 1;
+
+            while self.stack.len() >= 2 && self.stack[self.stack.len() - 2].size == self.stack[self.stack.len() - 1].size {
+                let right = self.stack.pop().unwrap();
+                let left = self.stack.pop().unwrap();
+                let merged = self.merge(left, right);
+                self.stack.push(merged);
+            }
+        }
+
+        /// Returns the tree size, the root and the audit path of the target leaf. The remaining
+        /// subtrees shrink from left to right and are joined from the right, which yields the
+        /// RFC 6962 split at the largest power of two below the size.
+        pub fn finish(mut self) -> (u64, [u8; 32], Vec<[u8; 32]>) {
+            let Some(mut right) = self.stack.pop() else {
+                let mut ctx = SHA256::new();
+                return (0, ctx.finalize(), Vec::new());
+            };
+            while let Some(left) = self.stack.pop() {
+                right = self.merge(left, right);
+            }
+            (self.count, right.hash, self.path)
+        }
+
+        fn merge(&mut self, left: Subtree, right: Subtree) -> Subtree {
+            let contains = |tree: &Subtree, index: u64| (tree.start..tree.start + tree.size).contains(&index);
+            match self.target {
+                Some(index) if contains(&left, index) => self.path.push(right.hash),
+                Some(index) if contains(&right, index) => self.path.push(left.hash),
+                _ => {}
+            }
+            Subtree {
+                start: left.start,
+                size: left.size + right.size,
+                hash: node_hash(&left.hash, &right.hash),
+            }
+        }
+    }
+
+    /// Checks an audit path for the leaf at `index` of a tree with `size` leaves, following the
+    /// verification algorithm of RFC 9162 section 2.1.3.2.
+    pub fn verify(index: u64, size: u64, leaf: &[u8; 32], path: &[[u8; 32]], root: &[u8; 32]) -> bool {
+        if index >= size {
+            return false;
+        }
+
+        let (mut fn_, mut sn) = (index, size - 1);
+        let mut r = *leaf;
+        for p in path {
+            if sn == 0 {
+                return false;
+            }
+            if fn_ & 1 == 1 || fn_ == sn {
+                r = node_hash(p, &r);
+                while fn_ & 1 == 0 && fn_ != 0 {
+                    fn_ >>= 1;
+                    sn >>= 1;
+                }
+            } else {
+                r = node_hash(&r, p);
+            }
+            fn_ >>= 1;
+            sn >>= 1;
+        }
+        sn == 0 && r == *root
+    }
+}
+
 const H: [u32; 8] = [
     0x6a09e667,
     0xbb67ae85,
@@ -229,3 +1517,70 @@ const K: [u32; 64] = [
     0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
     0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
 ];
+
+const H224: [u32; 8] = [
+    0xc1059ed8,
+    0x367cd507,
+    0x3070dd17,
+    0xf70e5939,
+    0xffc00b31,
+    0x68581511,
+    0x64f98fa7,
+    0xbefa4fa4,
+];
+
+const H384: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+
+const H512: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const H512_256: [u64; 8] = [
+    0x22312194fc2bf72c,
+    0x9f555fa3c84c64c2,
+    0x2393b86b6f53b151,
+    0x963877195940eabd,
+    0x96283ee2a88effe3,
+    0xbe5e1e2553863992,
+    0x2b0199fc2c85b8aa,
+    0x0eb72ddc81c52ca2,
+];
+
+const K512: [u64; 80] = [
+    0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
+    0x3956c25bf348b538, 0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118,
+    0xd807aa98a3030242, 0x12835b0145706fbe, 0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235, 0xc19bf174cf692694,
+    0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5,
+    0x983e5152ee66dfab, 0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2, 0xd5a79147930aa725, 0x06ca6351e003826f, 0x142929670a0e6e70,
+    0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed, 0x53380d139d95b3df,
+    0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+    0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30,
+    0xd192e819d6ef5218, 0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8, 0x1e376c085141ab53, 0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373, 0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+    0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b,
+    0xca273eceea26619c, 0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178,
+    0x06f067aa72176fba, 0x0a637dc5a2c898a6, 0x113f9804bef90dae, 0x1b710b35131c471b,
+    0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc, 0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
+];
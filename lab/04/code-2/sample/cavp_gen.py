@@ -0,0 +1,88 @@
+import hashlib
+import os
+import random
+
+# Writes sample/cavp/SHA3_*{ShortMsg,LongMsg}.rsp and
+# SHAKE*{ShortMsg,LongMsg,VariableOut}.rsp in the layout of the NIST CAVP
+# SHA3VS byte-oriented response files, with outputs from Python's hashlib
+# (OpenSSL), an implementation independent of ours. The official NIST files
+# use the same names and can replace these as they are.
+#
+# ShortMsg covers every length from 0 to one rate in bytes, as SHA3VS does.
+# LongMsg covers one byte either side of several rate multiples and
+# SHA3VS-style steps of 99 bytes. VariableOut squeezes every output length up
+# to two rates plus one byte, then one byte either side of larger multiples.
+#
+# Run from the crate root with:
+#     python3 sample/cavp_gen.py
+HASHES = {
+    # file name: (hashlib name, rate in bytes)
+    "SHA3_224": ("sha3_224", 144),
+    "SHA3_256": ("sha3_256", 136),
+    "SHA3_384": ("sha3_384", 104),
+    "SHA3_512": ("sha3_512", 72),
+}
+XOFS = {
+    # file name: (hashlib name, rate in bytes, security strength in bits)
+    "SHAKE128": ("shake_128", 168, 128),
+    "SHAKE256": ("shake_256", 136, 256),
+}
+HEADER = """\
+#  Generated by sample/cavp_gen.py with Python's hashlib, in the layout of the
+#  NIST CAVP {name} response file. Not the NIST file; that one can replace it.
+"""
+
+
+def long_lengths(rate):
+    lengths = {rate * k + d for k in (1, 2, 3, 4, 8, 16) for d in (-1, 0, 1)}
+    lengths |= {rate + 99 * i for i in range(1, 25)}
+    return sorted(lengths)
+
+
+def digest(algorithm, msg, out_len=None):
+    ctx = hashlib.new(algorithm, msg)
+    return ctx.hexdigest(out_len) if out_len is not None else ctx.hexdigest()
+
+
+def write(name, lines):
+    with open(f"sample/cavp/{name}.rsp", "w") as f:
+        f.write(HEADER.format(name=name))
+        f.write("\n".join(lines) + "\n")
+    print(f"wrote {name}.rsp: {sum(line.startswith(('MD', 'Output =')) for line in lines)} entries")
+
+
+def message_entries(algorithm, lengths, out_len, key):
+    lines = []
+    for length in lengths:
+        msg = rng.randbytes(length)
+        # SHA3VS writes a single 00 byte for the empty message.
+        lines += ["", f"Len = {length * 8}", f"Msg = {msg.hex() or '00'}", f"{key} = {digest(algorithm, msg, out_len)}"]
+    return lines
+
+
+rng = random.Random(2025)
+os.makedirs("sample/cavp", exist_ok=True)
+for name, (algorithm, rate) in HASHES.items():
+    header = ["", f"[L = {hashlib.new(algorithm).digest_size * 8}]"]
+    write(f"{name}ShortMsg", header + message_entries(algorithm, range(rate + 1), None, "MD"))
+    write(f"{name}LongMsg", header + message_entries(algorithm, long_lengths(rate), None, "MD"))
+
+for name, (algorithm, rate, strength) in XOFS.items():
+    header = ["", f"[Outputlen = {strength}]"]
+    out_len = strength // 8
+    write(f"{name}ShortMsg", header + message_entries(algorithm, range(rate + 1), out_len, "Output"))
+    write(f"{name}LongMsg", header + message_entries(algorithm, long_lengths(rate), out_len, "Output"))
+
+    out_lens = list(range(1, 2 * rate + 2)) + [rate * k + d for k in (3, 4, 8) for d in (-1, 0, 1)]
+    lines = [
+        "",
+        "[Tested for Output of byte-oriented messages]",
+        f"[Input Length = {strength}]",
+        f"[Minimum Output Length (bits) = {out_lens[0] * 8}]",
+        f"[Maximum Output Length (bits) = {out_lens[-1] * 8}]",
+    ]
+    for count, length in enumerate(out_lens):
+        msg = rng.randbytes(strength // 8)
+        lines += ["", f"COUNT = {count}", f"Outputlen = {length * 8}", f"Msg = {msg.hex()}",
+                  f"Output = {digest(algorithm, msg, length)}"]
+    write(f"{name}VariableOut", lines)
@@ -0,0 +1,102 @@
+import os
+import re
+import subprocess
+import sys
+
+# Known-answer checks for the SHA-3 hashes and SHAKE XOFs.
+#
+# With no arguments, the FIPS 202 example messages from the NIST CSRC examples
+# page are hashed: the empty message, "abc" and the 1600-bit message of 0xa3
+# bytes, which spans more than one block at every rate. The 4096-bit SHAKE128
+# example checks squeezing across many permutations via its first and last 32
+# bytes. These run every time.
+#
+# The byte-oriented entries of CAVP-format response files are then replayed
+# against the binary. With no arguments these are the SHA3_*{ShortMsg,LongMsg}
+# and SHAKE*{ShortMsg,LongMsg,VariableOut} files in sample/cavp/ (written by
+# sample/cavp_gen.py; the NIST files can replace them), which cross every rate
+# boundary for both input and output, and a missing file is an error.
+# Otherwise each argument is a response file.
+#
+# Run from the crate root with:
+#     python3 sample/vectors.py [SHA3_*.rsp SHAKE*.rsp ...]
+A3 = bytes([0xa3]) * 200
+
+examples = [
+    ("sha3-224", None, b"", "6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7"),
+    ("sha3-224", None, b"abc", "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf"),
+    ("sha3-224", None, A3, "9376816aba503f72f96ce7eb65ac095deee3be4bf9bbc2a1cb7e11e0"),
+    ("sha3-256", None, b"", "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"),
+    ("sha3-256", None, b"abc", "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"),
+    ("sha3-256", None, A3, "79f38adec5c20307a98ef76e8324afbfd46cfd81b22e3973c65fa1bd9de31787"),
+    ("sha3-384", None, b"", "0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2a"
+                            "c3713831264adb47fb6bd1e058d5f004"),
+    ("sha3-384", None, b"abc", "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b2"
+                               "98d88cea927ac7f539f1edf228376d25"),
+    ("sha3-384", None, A3, "1881de2ca7e41ef95dc4732b8f5f002b189cc1e42b74168ed1732649ce1dbcdd"
+                           "76197a31fd55ee989f2d7050dd473e8f"),
+    ("sha3-512", None, b"", "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a6"
+                            "15b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26"),
+    ("sha3-512", None, b"abc", "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e"
+                               "10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"),
+    ("sha3-512", None, A3, "e76dfad22084a8b1467fcf2ffa58361bec7628edf5f3fdc0e4805dc48caeeca8"
+                           "1b7c13c30adf52a3659584739a2df46be589c51ca1a4a8416df6545a1ce8ba00"),
+    ("shake128", 32, b"", "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"),
+    ("shake128", 32, A3, "131ab8d2b594946b9c81333f9bb6e0ce75c3b93104fa3469d3917457385da037"),
+    ("shake256", 64, b"", "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f"
+                          "d75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be"),
+    ("shake256", 64, A3, "cd8a920ed141aa0407a22d59288652e9d9f1a7ee0c1e7c1ca699424da84a904d"
+                         "2d700caae7396ece96604440577da4f3aa22aeb8857f961c4cd8e06f0ae6610b"),
+]
+
+# The first and last 32 bytes of the 512-byte SHAKE128 output for the 0xa3 message.
+long_output = ("131ab8d2b594946b9c81333f9bb6e0ce75c3b93104fa3469d3917457385da037",
+               "44c9fb359fd56ac0a9a75a743cff6862f17d7259ab075216c0699511643b6439")
+
+
+def run(algorithm, length, msg):
+    args = ["target/release/code-2", "-a", algorithm]
+    args += ["--length", str(length)] if length is not None and algorithm.startswith("shake") else []
+    output = subprocess.run(args, input=msg, capture_output=True, check=True).stdout
+    return output.split()[0].decode()
+
+
+def parse_rsp(path):
+    name = path.split("/")[-1]
+    algorithm = re.match(r"(SHA3_\d+|SHAKE\d+)", name).group(1).lower().replace("_", "-")
+    vectors, length, bits, msg = [], None, None, None
+    with open(path) as file:
+        for line in file:
+            key, _, value = (part.strip(" []\n") for part in line.partition("="))
+            if key in ("L", "Outputlen"):
+                length = int(value) // 8
+            elif key == "Len":
+                bits = int(value)
+            elif key == "Msg":
+                msg = bytes.fromhex(value)[:bits // 8] if bits is not None else bytes.fromhex(value)
+            elif key in ("MD", "Output"):
+                vectors.append((algorithm, length, msg, value))
+    return vectors
+
+
+subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+
+paths = sys.argv[1:]
+if not paths:
+    names = [f"SHA3_{bits}{kind}" for bits in (224, 256, 384, 512) for kind in ("ShortMsg", "LongMsg")]
+    names += [f"SHAKE{bits}{kind}" for bits in (128, 256) for kind in ("ShortMsg", "LongMsg", "VariableOut")]
+    paths = [f"sample/cavp/{name}.rsp" for name in names]
+    missing = [path for path in paths if not os.path.exists(path)]
+    if missing:
+        sys.exit(f"missing response files: {', '.join(missing)} (run sample/cavp_gen.py)")
+
+vectors = examples + [vector for path in paths for vector in parse_rsp(path)]
+
+for algorithm, length, msg, expected in vectors:
+    output = run(algorithm, length, msg)
+    assert output == expected, f"{algorithm} over {len(msg)} bytes: got {output}"
+
+output = run("shake128", 512, A3)
+assert (output[:64], output[-64:]) == long_output, f"shake128 with 512 output bytes: got {output}"
+
+print(f"{len(vectors)} vectors from {len(paths)} response files and the FIPS 202 examples OK")
@@ -0,0 +1,298 @@
+use std::io;
+use std::io::{Read, Write};
+use std::{env, fs, process};
+
+fn main() {
+    let mut algorithm = String::from("sha3-256");
+    let mut length = None;
+    let mut paths = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "-a" | "--algorithm" => algorithm = value(),
+            "--length" => length = Some(value().parse().unwrap_or_else(|_| usage())),
+            "-h" | "--help" => usage(),
+            "-" => paths.push(arg),
+            _ if arg.starts_with('-') => usage(),
+            _ => paths.push(arg),
+        }
+    }
+    if paths.is_empty() {
+        paths.push(String::from("-"));
+    }
+
+    if Hasher::new(&algorithm, length).is_none() {
+        eprintln!("unknown algorithm {}, or --length given for a fixed-length hash", algorithm);
+        process::exit(1);
+    }
+
+    let mut ok = true;
+    let mut stdout = io::stdout().lock();
+    for path in &paths {
+        match digest(&algorithm, length, path) {
+            Ok(digest) => writeln!(stdout, "{}  {}", to_hex(&digest), path).unwrap(),
+            Err(err) => {
+                eprintln!("code-2: {}: {}", path, err);
+                ok = false;
+            }
+        }
+    }
+    if !ok {
+        process::exit(1);
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("\
+usage: code-2 [-a <sha3-224|sha3-256|sha3-384|sha3-512|shake128|shake256>] [--length <n>] [file...]
+
+Prints `<digest>  <name>` per file; a missing file or `-` reads stdin. --length sets the
+number of output bytes of the SHAKE XOFs (32 for SHAKE128 and 64 for SHAKE256 by default).");
+    process::exit(1);
+}
+
+fn digest(algorithm: &str, length: Option<usize>, path: &str) -> io::Result<Vec<u8>> {
+    let mut file: Box<dyn Read> = if path == "-" {
+        Box::new(io::stdin())
+    } else {
+        Box::new(fs::File::open(path)?)
+    };
+    let mut ctx = Hasher::new(algorithm, length).unwrap();
+    let mut buf = vec![0u8; 640000];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        ctx.update(&buf[..n]);
+    }
+    Ok(ctx.finalize())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// A SHA-3 hash or SHAKE XOF chosen at runtime; the XOF carries its output length.
+enum Hasher {
+    Sha3(SHA3),
+    Shake(Shake, usize),
+}
+
+impl Hasher {
+    fn new(algorithm: &str, length: Option<usize>) -> Option<Self> {
+        match (algorithm, length) {
+            ("sha3-224", None) => Some(Hasher::Sha3(SHA3::new(224))),
+            ("sha3-256", None) => Some(Hasher::Sha3(SHA3::new(256))),
+            ("sha3-384", None) => Some(Hasher::Sha3(SHA3::new(384))),
+            ("sha3-512", None) => Some(Hasher::Sha3(SHA3::new(512))),
+            ("shake128", _) => Some(Hasher::Shake(Shake::new(128), length.unwrap_or(32))),
+            ("shake256", _) => Some(Hasher::Shake(Shake::new(256), length.unwrap_or(64))),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, text: &[u8]) {
+        match self {
+            Hasher::Sha3(ctx) => ctx.update(text),
+            Hasher::Shake(ctx, _) => ctx.update(text),
+        }
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Sha3(ctx) => ctx.finalize(),
+            Hasher::Shake(ctx, len) => {
+                let mut res = vec![0u8; len];
+                ctx.finalize().squeeze(&mut res);
+                res
+            }
+        }
+    }
+}
+
+/// SHA3-224/256/384/512 (FIPS 202).
+struct SHA3 {
+    sponge: Keccak,
+    out_len: usize,
+}
+
+impl SHA3 {
+    /// `bits` is the digest size; the capacity is twice that.
+    fn new(bits: usize) -> Self {
+        SHA3 {
+            sponge: Keccak::new(200 - bits / 4, 0x06),
+            out_len: bits / 8,
+        }
+    }
+
+    fn update(&mut self, text: &[u8]) {
+        self.sponge.update(text);
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        let mut res = vec![0u8; self.out_len];
+        self.sponge.finalize().squeeze(&mut res);
+        res
+    }
+}
+
+/// The SHAKE128 and SHAKE256 extendable-output functions (FIPS 202).
+struct Shake {
+    sponge: Keccak,
+}
+
+impl Shake {
+    /// `bits` is the security strength, 128 or 256; the capacity is twice that.
+    fn new(bits: usize) -> Self {
+        Shake {
+            sponge: Keccak::new(200 - bits / 4, 0x1f),
+        }
+    }
+
+    fn update(&mut self, text: &[u8]) {
+        self.sponge.update(text);
+    }
+
+    /// Ends the input and returns the output stream. Taking `self` means nothing can be
+    /// absorbed once squeezing has started.
+    fn finalize(self) -> Squeezer {
+        self.sponge.finalize()
+    }
+}
+
+/// The Keccak sponge over Keccak-f[1600] with multi-rate padding.
+struct Keccak {
+    state: [u64; 25],
+    /// Input not yet absorbed; large enough for the widest rate (SHAKE128).
+    buffer: [u8; 168],
+    /// The number of buffered bytes.
+    buffer_len: usize,
+    rate: usize,
+    /// The domain separation bits followed by the first padding bit.
+    suffix: u8,
+}
+
+impl Keccak {
+    fn new(rate: usize, suffix: u8) -> Self {
+        Keccak {
+            state: [0u64; 25],
+            buffer: [0u8; 168],
+            buffer_len: 0,
+            rate,
+            suffix,
+        }
+    }
+
+    fn update(&mut self, mut text: &[u8]) {
+        if self.buffer_len > 0 {
+            let take = text.len().min(self.rate - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&text[..take]);
+            self.buffer_len += take;
+            text = &text[take..];
+            if self.buffer_len < self.rate {
+                return;
+            }
+            let temp = self.buffer;
+            self.absorb_block(&temp[..self.rate]);
+            self.buffer_len = 0;
+        }
+
+        let mut chunks = text.chunks_exact(self.rate);
+        chunks.by_ref().for_each(|chunk| self.absorb_block(chunk));
+        let rest = chunks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+    }
+
+    /// Appends the suffix and the final padding bit, absorbs the last block and switches to
+    /// squeezing.
+    fn finalize(mut self) -> Squeezer {
+        self.buffer[self.buffer_len..self.rate].fill(0);
+        self.buffer[self.buffer_len] ^= self.suffix;
+        self.buffer[self.rate - 1] ^= 0x80;
+        let temp = self.buffer;
+        self.absorb_block(&temp[..self.rate]);
+
+        Squeezer {
+            state: self.state,
+            offset: 0,
+            rate: self.rate,
+        }
+    }
+
+    fn absorb_block(&mut self, block: &[u8]) {
+        self.state.iter_mut().zip(block.chunks(8)).for_each(|(lane, bytes)| {
+            *lane ^= u64::from_le_bytes(bytes.try_into().unwrap());
+        });
+        keccak_f(&mut self.state);
+    }
+}
+
+/// The squeezing phase of a finished Keccak sponge.
+struct Squeezer {
+    state: [u64; 25],
+    /// The offset of the next output byte within the rate.
+    offset: usize,
+    rate: usize,
+}
+
+impl Squeezer {
+    /// Fills `out` with the next output bytes. Later calls continue the same output stream,
+    /// so squeezing in pieces matches one large squeeze.
+    fn squeeze(&mut self, out: &mut [u8]) {
+        for byte in out.iter_mut() {
+            if self.offset == self.rate {
+                keccak_f(&mut self.state);
+                self.offset = 0;
+            }
+            *byte = (self.state[self.offset / 8] >> (8 * (self.offset % 8))) as u8;
+            self.offset += 1;
+        }
+    }
+}
+
+/// Keccak-f[1600]; lane (x, y) of the 5x5 state is `a[x + 5 * y]`.
+fn keccak_f(a: &mut [u64; 25]) {
+    for rc in RC {
+        // Theta: XOR each lane with the parities of two neighbouring columns.
+        let c: [u64; 5] = std::array::from_fn(|x| a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20]);
+        let d: [u64; 5] = std::array::from_fn(|x| c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1));
+        a.iter_mut().enumerate().for_each(|(i, lane)| *lane ^= d[i % 5]);
+
+        // Rho and pi: rotate every lane and move (x, y) to (y, 2x + 3y).
+        let mut b = [0u64; 25];
+        a.iter().enumerate().for_each(|(i, lane)| {
+            let (x, y) = (i % 5, i / 5);
+            b[y + 5 * ((2 * x + 3 * y) % 5)] = lane.rotate_left(RHO[i]);
+        });
+
+        // Chi: the only non-linear step, along each row.
+        a.iter_mut().enumerate().for_each(|(i, lane)| {
+            let (x, row) = (i % 5, i - i % 5);
+            *lane = b[i] ^ (!b[row + (x + 1) % 5] & b[row + (x + 2) % 5]);
+        });
+
+        // Iota
+        a[0] ^= rc;
+    }
+}
+
+const RC: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+    0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+/// Rotation offsets of the rho step, indexed by `x + 5 * y`.
+const RHO: [u32; 25] = [
+    0, 1, 62, 28, 27,
+    36, 44, 6, 55, 20,
+    3, 10, 43, 25, 39,
+    41, 45, 15, 21, 8,
+    18, 2, 61, 56, 14,
+];
@@ -0,0 +1,109 @@
+# Generates keys with `code-1 --keygen`, checks every field against the RSA
+# relations, and round-trips keys of every size --keygen offers through
+# code-1 (encrypt) and code-2 (decrypt) as well as the `cryptography` OAEP
+# implementation, so keygen never writes a layout the tools cannot read.
+#
+# Run from the crate root with:
+#     python3 sample/keygen.py [rounds]
+
+import os
+import subprocess
+import sys
+import tempfile
+
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import padding, rsa
+
+ENCRYPT = "target/release/code-1"
+DECRYPT = "../code-2/target/release/code-2"
+
+
+def is_probable_prime(n, rounds=32):
+    d, s = n - 1, 0
+    while d % 2 == 0:
+        d, s = d // 2, s + 1
+    for _ in range(rounds):
+        a = int.from_bytes(os.urandom(n.bit_length() // 8)) % (n - 3) + 2
+        x = pow(a, d, n)
+        if x in (1, n - 1):
+            continue
+        for _ in range(s - 1):
+            x = x * x % n
+            if x == n - 1:
+                break
+        else:
+            return False
+    return True
+
+
+def split(data, widths):
+    out, pos = [], 0
+    for w in widths:
+        out.append(int.from_bytes(data[pos:pos + w]))
+        pos += w
+    assert pos == len(data), "unexpected key file length"
+    return out
+
+
+def check_key(bits, enc_path, dec_path):
+    k = bits // 8
+    enc = open(enc_path, "rb").read()
+    assert enc[:16] == bits.to_bytes(4) + bytes(12)
+    n, e, d = split(enc[16:], [k, k, k])
+    p, q, n2, d2, dp, dq, qinv = split(
+        open(dec_path, "rb").read(), [k // 2, k // 2, k, k, k // 2, k // 2, k // 2]
+    )
+    assert (n, d) == (n2, d2)
+    assert e == 65537
+    assert n == p * q and n.bit_length() == bits
+    assert is_probable_prime(p) and is_probable_prime(q)
+    assert abs(p - q).bit_length() > bits // 2 - 100
+    assert e * d % ((p - 1) * (q - 1)) == 1
+    assert dp == d % (p - 1) and dq == d % (q - 1)
+    assert q * qinv % p == 1
+    return rsa.RSAPrivateNumbers(
+        p, q, d, dp, dq, qinv, rsa.RSAPublicNumbers(e, n)
+    ).private_key()
+
+
+def main():
+    rounds = int(sys.argv[1]) if len(sys.argv) > 1 else 4
+    subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+    subprocess.run(
+        ["cargo", "build", "--release", "--quiet"], cwd="../code-2", check=True
+    )
+    oaep = padding.OAEP(
+        mgf=padding.MGF1(algorithm=hashes.SHA256()),
+        algorithm=hashes.SHA256(),
+        label=None,
+    )
+
+    with tempfile.TemporaryDirectory() as tmp:
+        enc_path = os.path.join(tmp, "encrypt.key")
+        dec_path = os.path.join(tmp, "decrypt.key")
+
+        for i in range(rounds):
+            for bits in (2048, 3072, 4096):
+                subprocess.run([ENCRYPT, "--keygen", str(bits), enc_path, dec_path], check=True)
+                key = check_key(bits, enc_path, dec_path)
+                max_len = min(bits // 8 - 66, 255)
+                message = os.urandom(i * max_len // max(rounds - 1, 1))
+
+                stdin = open(enc_path, "rb").read() + bytes([len(message)]) + message
+                ciphertext = subprocess.run(
+                    [ENCRYPT], input=stdin, capture_output=True, check=True
+                ).stdout
+                assert key.decrypt(ciphertext, oaep) == message
+
+                stdin = open(dec_path, "rb").read() + key.public_key().encrypt(message, oaep)
+                plaintext = subprocess.run(
+                    [DECRYPT], input=stdin, capture_output=True, check=True
+                ).stdout
+                assert plaintext == message
+                print(f"{bits}-bit key OK")
+
+    print(f"{rounds} round trips OK")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,207 @@
+# Checks the PKCS#1 v1.5 paths of code-1 and code-2 against `cryptography`
+# and the OpenSSL command line tool:
+#   - `code-2 --sign --pkcs1` matches the deterministic signatures byte for
+#     byte, `code-1 --verify --pkcs1` accepts them and rejects malformed
+#     encodings, including a Bleichenbacher'06 cube-root forgery for e = 3;
+#   - `code-1 --pkcs1` ciphertexts decrypt with `cryptography`;
+#   - `code-2 --pkcs1` decrypts valid ciphertexts and, for malformed ones,
+#     returns the same synthetic message as OpenSSL's implicit rejection.
+#
+# Run from the crate root with:
+#     python3 sample/pkcs1.py [rounds]
+
+import hashlib
+import os
+import random
+import subprocess
+import sys
+import tempfile
+
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import padding, rsa
+
+ENCRYPT = "target/release/code-1"
+DECRYPT = "../code-2/target/release/code-2"
+DIGEST_INFO = bytes.fromhex("3031300d060960864801650304020105000420")
+
+
+def key_files(tmp, key):
+    numbers = key.private_numbers()
+    n, e = numbers.public_numbers.n, numbers.public_numbers.e
+    k = key.key_size // 8
+    enc = key.key_size.to_bytes(4) + bytes(12) + b"".join(
+        x.to_bytes(k) for x in [n, e, numbers.d]
+    )
+    dec = b"".join(x.to_bytes(w) for x, w in [
+        (numbers.p, k // 2), (numbers.q, k // 2), (n, k), (numbers.d, k),
+        (numbers.dmp1, k // 2), (numbers.dmq1, k // 2), (numbers.iqmp, k // 2),
+    ])
+    paths = [os.path.join(tmp, name) for name in ["encrypt.key", "decrypt.key", "key.pem"]]
+    pem = key.private_bytes(
+        serialization.Encoding.PEM,
+        serialization.PrivateFormat.PKCS8,
+        serialization.NoEncryption(),
+    )
+    for path, data in zip(paths, [enc, dec, pem]):
+        with open(path, "wb") as f:
+            f.write(data)
+    return paths, dec
+
+
+def verify(tmp, enc_path, sig, msg):
+    sig_path = os.path.join(tmp, "signature")
+    with open(sig_path, "wb") as f:
+        f.write(sig)
+    out = subprocess.run(
+        [ENCRYPT, "--verify", enc_path, sig_path, "--pkcs1"], input=msg, capture_output=True
+    )
+    assert out.returncode in (0, 1), out.stderr
+    return out.returncode == 0
+
+
+def raw(key, em):
+    numbers = key.private_numbers()
+    n = numbers.public_numbers.n
+    return pow(int.from_bytes(em), numbers.d, n).to_bytes(key.key_size // 8)
+
+
+def icbrt(x):
+    # Smallest integer r with r^3 >= x.
+    r = 1 << ((x.bit_length() + 2) // 3)
+    while True:
+        s = (2 * r + x // (r * r)) // 3
+        if s >= r:
+            break
+        r = s
+    while r ** 3 < x:
+        r += 1
+    return r
+
+
+def check_signatures(tmp, key, rng, rounds):
+    (enc_path, dec_path, _), _ = key_files(tmp, key)
+    k = key.key_size // 8
+    for _ in range(rounds):
+        msg = rng.randbytes(rng.randrange(2000))
+        expected = key.sign(msg, padding.PKCS1v15(), hashes.SHA256())
+        out = subprocess.run(
+            [DECRYPT, "--sign", dec_path, "--pkcs1"], input=msg, capture_output=True, check=True
+        )
+        assert out.stdout == expected
+        assert verify(tmp, enc_path, expected, msg)
+        assert not verify(tmp, enc_path, expected, msg + b"x")
+
+    msg = b"forge me"
+    h = hashlib.sha256(msg).digest()
+    t = DIGEST_INFO + h
+    good = b"\x00\x01" + b"\xff" * (k - len(t) - 3) + b"\x00" + t
+    assert verify(tmp, enc_path, raw(key, good), msg)
+    malformed = {
+        "block type 2": b"\x00\x02" + good[2:],
+        "0xfe in padding": good[:5] + b"\xfe" + good[6:],
+        "short padding, trailing garbage": b"\x00\x01" + b"\xff" * 7 + b"\x00" + t
+        + bytes(k - len(t) - 10),
+        "NULL parameters omitted": b"\x00\x01" + b"\xff" * (k - len(t) - 1) + b"\x00"
+        + bytes.fromhex("302f300b0609608648016503040201") + b"\x04\x20" + h,
+        "SHA-1 OID": b"\x00\x01" + b"\xff" * (k - 50) + b"\x00"
+        + bytes.fromhex("3021300906052b0e03021a05000414") + h,
+        "wrong digest": good[:-1] + bytes([good[-1] ^ 1]),
+    }
+    for name, em in malformed.items():
+        assert len(em) == k, name
+        assert not verify(tmp, enc_path, raw(key, em), msg), name
+
+
+def check_cube_root_forgery(tmp):
+    # Bleichenbacher'06: with e = 3, a value whose cube starts with a short
+    # but well-formed prefix fools verifiers that stop parsing after the hash.
+    key = rsa.generate_private_key(public_exponent=3, key_size=3072)
+    (enc_path, _, _), _ = key_files(tmp, key)
+    k = key.key_size // 8
+    msg = b"pay mallory"
+    prefix = b"\x00\x01\xff\x00" + DIGEST_INFO + hashlib.sha256(msg).digest()
+    garbage_bits = 8 * (k - len(prefix))
+    target = int.from_bytes(prefix) << garbage_bits
+    forged = icbrt(target)
+    assert (forged ** 3) >> garbage_bits == int.from_bytes(prefix)
+    assert not verify(tmp, enc_path, forged.to_bytes(k), msg)
+
+    sig = key.sign(msg, padding.PKCS1v15(), hashes.SHA256())
+    assert verify(tmp, enc_path, sig, msg)
+
+
+def check_encryption(tmp, key, rng, rounds):
+    (enc_path, _, pem_path), dec = key_files(tmp, key)
+    numbers = key.private_numbers()
+    n, e = numbers.public_numbers.n, numbers.public_numbers.e
+    k = key.key_size // 8
+    with open(enc_path, "rb") as f:
+        enc = f.read()
+
+    for _ in range(rounds):
+        msg = rng.randbytes(rng.randrange(min(k - 11, 255) + 1))
+        out = subprocess.run(
+            [ENCRYPT, "--pkcs1"], input=enc + bytes([len(msg)]) + msg,
+            capture_output=True, check=True,
+        )
+        assert key.decrypt(out.stdout, padding.PKCS1v15()) == msg
+
+        c = key.public_key().encrypt(msg, padding.PKCS1v15())
+        out = subprocess.run([DECRYPT, "--pkcs1"], input=dec + c, capture_output=True, check=True)
+        assert out.stdout == msg
+
+    if k - 10 <= 255:
+        out = subprocess.run(
+            [ENCRYPT, "--pkcs1"], input=enc + bytes([k - 10]) + bytes(k - 10), capture_output=True
+        )
+        assert out.returncode != 0 and out.stdout == b""
+
+    # Malformed plaintexts: the output must match OpenSSL's implicit rejection.
+    ems = [os.urandom(k - 1).rjust(k, b"\x00") for _ in range(rounds)]
+    good = b"\x00\x02" + b"\x11" * 8 + b"\x00" + b"message"
+    good = good[:2] + b"\x11" * (k - len(good)) + good[2:]
+    ems += [
+        b"\x01" + good[1:],
+        b"\x00\x01" + good[2:],
+        good[:2] + b"\x11" * (k - 10) + b"\x00" * 8,
+        good[:2] + b"\x11" * (k - 3) + b"\x11",
+        good[:5] + b"\x00" + good[6:],
+        good,
+    ]
+    for em in ems:
+        c = pow(int.from_bytes(em), e, n).to_bytes(k)
+        c_path = os.path.join(tmp, "ciphertext")
+        with open(c_path, "wb") as f:
+            f.write(c)
+        expected = subprocess.run(
+            ["openssl", "pkeyutl", "-decrypt", "-inkey", pem_path, "-in", c_path,
+             "-pkeyopt", "rsa_padding_mode:pkcs1"],
+            capture_output=True, check=True,
+        ).stdout
+        out = subprocess.run([DECRYPT, "--pkcs1"], input=dec + c, capture_output=True, check=True)
+        assert out.stdout == expected, em.hex()
+    assert expected == b"message"
+
+
+def main():
+    rounds = int(sys.argv[1]) if len(sys.argv) > 1 else 4
+    rng = random.Random(2025)
+    subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+    subprocess.run(
+        ["cargo", "build", "--release", "--quiet"], cwd="../code-2", check=True
+    )
+
+    with tempfile.TemporaryDirectory() as tmp:
+        for bits in [1024, 2048, 3072]:
+            key = rsa.generate_private_key(public_exponent=65537, key_size=bits)
+            check_signatures(tmp, key, rng, rounds)
+            check_encryption(tmp, key, rng, rounds)
+            print(f"{bits}-bit PKCS#1 v1.5 OK")
+        check_cube_root_forgery(tmp)
+        print("e = 3 forgery rejected")
+
+    print("pkcs1 OK")
+
+
+if __name__ == "__main__":
+    main()
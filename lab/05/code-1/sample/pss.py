@@ -0,0 +1,156 @@
+# Runs the RSASSA-PSS vectors in sample/pss_vectors.json through
+# `code-1 --verify`, checks that `code-2 --sign` reproduces the deterministic
+# (zero-length salt) signatures byte for byte, and cross-checks freshly
+# signed random messages with the `cryptography` verifier.
+#
+# sample/pss_vectors.json is generated locally by sample/pss_vectors.py. The
+# published Wycheproof files (e.g. rsa_pss_2048_sha256_mgf1_32_test.json from
+# https://github.com/C2SP/wycheproof/tree/main/testvectors_v1) are run too
+# when copied into sample/wycheproof/; groups other than SHA-256 with
+# MGF1-SHA-256 are skipped, which also rules out RSA Labs' SHA-1 pss-vect.txt.
+#
+# Run from the crate root with:
+#     python3 sample/pss.py [rounds]
+
+import glob
+import json
+import os
+import random
+import subprocess
+import sys
+import tempfile
+
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import padding, rsa
+
+ENCRYPT = "target/release/code-1"
+DECRYPT = "../code-2/target/release/code-2"
+
+
+def write_keys(tmp, g):
+    k = g["keySize"] // 8
+    value = lambda name: int(g[name], 16)
+    enc_path = os.path.join(tmp, "encrypt.key")
+    dec_path = os.path.join(tmp, "decrypt.key")
+    with open(enc_path, "wb") as f:
+        f.write(g["keySize"].to_bytes(4) + bytes(12))
+        for name in ["n", "e", "d"]:
+            f.write(value(name).to_bytes(k))
+    with open(dec_path, "wb") as f:
+        for name, width in [("p", k // 2), ("q", k // 2), ("n", k), ("d", k),
+                            ("dp", k // 2), ("dq", k // 2), ("qinv", k // 2)]:
+            f.write(value(name).to_bytes(width))
+    key = rsa.RSAPrivateNumbers(
+        value("p"), value("q"), value("d"), value("dp"), value("dq"), value("qinv"),
+        rsa.RSAPublicNumbers(value("e"), value("n")),
+    ).private_key()
+    return enc_path, dec_path, key
+
+
+def verify(enc_path, sig, msg, s_len, tmp):
+    sig_path = os.path.join(tmp, "signature")
+    with open(sig_path, "wb") as f:
+        f.write(sig)
+    out = subprocess.run(
+        [ENCRYPT, "--verify", enc_path, sig_path, "--salt-len", str(s_len)],
+        input=msg, capture_output=True,
+    )
+    assert out.returncode in (0, 1), out.stderr
+    return out.returncode == 0
+
+
+def write_public_key(tmp, n, e):
+    # code-1 --verify only reads n and e, so d is left as zeros.
+    k = (n.bit_length() + 7) // 8
+    enc_path = os.path.join(tmp, "public.key")
+    with open(enc_path, "wb") as f:
+        f.write((8 * k).to_bytes(4) + bytes(12) + n.to_bytes(k) + e.to_bytes(k) + bytes(k))
+    return enc_path
+
+
+def run_wycheproof(tmp, path):
+    # Both the v0 (n, e at group level) and the v1 (publicKey object) layouts.
+    with open(path) as f:
+        vectors = json.load(f)
+    run = skipped = 0
+    for g in vectors["testGroups"]:
+        if g.get("sha") != "SHA-256" or g.get("mgfSha") != "SHA-256":
+            skipped += len(g["tests"])
+            continue
+        key = g.get("publicKey", g)
+        n = int(key.get("modulus", key.get("n")), 16)
+        e = int(key.get("publicExponent", key.get("e")), 16)
+        enc_path = write_public_key(tmp, n, e)
+        for t in g["tests"]:
+            msg, sig = bytes.fromhex(t["msg"]), bytes.fromhex(t["sig"])
+            ok = verify(enc_path, sig, msg, g["sLen"], tmp)
+            # "acceptable" cases may go either way
+            if t["result"] != "acceptable":
+                assert ok == (t["result"] == "valid"), f"{path} tcId {t['tcId']}: {t['comment']}"
+            run += 1
+    print(f"{os.path.basename(path)}: {run} vectors OK, {skipped} skipped")
+
+
+def sign(dec_path, msg, s_len):
+    out = subprocess.run(
+        [DECRYPT, "--sign", dec_path, "--salt-len", str(s_len)],
+        input=msg, capture_output=True,
+    )
+    assert out.returncode == 0, out.stderr
+    return out.stdout
+
+
+def main():
+    rounds = int(sys.argv[1]) if len(sys.argv) > 1 else 3
+    rng = random.Random(2025)
+    subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+    subprocess.run(
+        ["cargo", "build", "--release", "--quiet"], cwd="../code-2", check=True
+    )
+    with open("sample/pss_vectors.json") as f:
+        vectors = json.load(f)
+
+    with tempfile.TemporaryDirectory() as tmp:
+        for g in vectors["testGroups"]:
+            enc_path, dec_path, key = write_keys(tmp, g)
+            s_len = g["sLen"]
+
+            for t in g["tests"]:
+                msg, sig = bytes.fromhex(t["msg"]), bytes.fromhex(t["sig"])
+                ok = verify(enc_path, sig, msg, s_len, tmp)
+                assert ok == (t["result"] == "valid"), f"tcId {t['tcId']}: {t['comment']}"
+                if s_len == 0 and t["result"] == "valid":
+                    assert sign(dec_path, msg, 0) == sig, f"tcId {t['tcId']}"
+
+            pss = padding.PSS(mgf=padding.MGF1(hashes.SHA256()), salt_length=s_len)
+            for _ in range(rounds):
+                msg = rng.randbytes(rng.randrange(2000))
+                sig = sign(dec_path, msg, s_len)
+                key.public_key().verify(sig, msg, pss, hashes.SHA256())
+                assert verify(enc_path, sig, msg, s_len, tmp)
+                if s_len > 0:
+                    assert not verify(enc_path, sig, msg, s_len - 1, tmp)
+
+            # The largest salt that still fits, and one byte more.
+            max_salt = g["keySize"] // 8 - 32 - 2
+            sig = sign(dec_path, b"max salt", max_salt)
+            assert verify(enc_path, sig, b"max salt", max_salt, tmp)
+            out = subprocess.run(
+                [DECRYPT, "--sign", dec_path, "--salt-len", str(max_salt + 1)],
+                input=b"", capture_output=True,
+            )
+            assert out.returncode != 0 and out.stdout == b""
+
+            print(f"{g['keySize']}-bit, sLen {s_len}: {len(g['tests'])} vectors OK")
+
+        published = sorted(glob.glob("sample/wycheproof/*.json"))
+        for path in published:
+            run_wycheproof(tmp, path)
+        if not published:
+            print("no Wycheproof files in sample/wycheproof/, published vectors not run")
+
+    print("pss OK")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,146 @@
+# Generates sample/pss_vectors.json, Wycheproof-style RSASSA-PSS test groups
+# for SHA-256 with MGF1-SHA-256. Valid signatures come from `cryptography`;
+# invalid ones either damage a valid signature or sign a deliberately
+# malformed EMSA-PSS encoding with the raw private exponent.
+#
+# The JSON file is checked in; rerunning this script replaces every key.
+#
+# Run from the crate root with:
+#     python3 sample/pss_vectors.py
+
+import hashlib
+import json
+import random
+
+from cryptography.exceptions import InvalidSignature
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import padding, rsa
+
+GROUPS = [(1024, 20), (2048, 0), (2048, 32), (3072, 32), (4096, 64)]
+H_LEN = 32
+
+
+def mgf1(seed, length):
+    out = b""
+    for counter in range((length + H_LEN - 1) // H_LEN):
+        out += hashlib.sha256(seed + counter.to_bytes(4)).digest()
+    return out[:length]
+
+
+def xor(a, b):
+    return bytes(x ^ y for x, y in zip(a, b))
+
+
+def encode(msg, salt, em_bits, flaw=None):
+    # EMSA-PSS-ENCODE (RFC 8017 9.1.1), optionally breaking one step.
+    em_len = (em_bits + 7) // 8
+    m_hash = hashlib.sha256(msg).digest()
+    h = hashlib.sha256(bytes(8) + m_hash + salt).digest()
+    if flaw == "hash":
+        h = hashlib.sha256(bytes(8) + m_hash + salt + b"\0").digest()
+    ps = bytearray(em_len - len(salt) - H_LEN - 2)
+    if flaw == "ps":
+        ps[len(ps) // 2] = 0x01
+    separator = b"\x02" if flaw == "separator" else b"\x01"
+    db = bytearray(xor(bytes(ps) + separator + salt, mgf1(h, em_len - H_LEN - 1)))
+    db[0] &= 0xFF >> (8 * em_len - em_bits)
+    if flaw == "top-bit":
+        db[0] |= 0x80
+    trailer = b"\xbb" if flaw == "trailer" else b"\xbc"
+    return bytes(db) + h + trailer
+
+
+def raw_sign(key, em):
+    numbers = key.private_numbers()
+    n = numbers.public_numbers.n
+    return pow(int.from_bytes(em), numbers.d, n).to_bytes((n.bit_length() + 7) // 8)
+
+
+def group(rng, bits, s_len):
+    key = rsa.generate_private_key(public_exponent=65537, key_size=bits)
+    numbers = key.private_numbers()
+    n = numbers.public_numbers.n
+    k = bits // 8
+    em_bits = n.bit_length() - 1
+    pss = padding.PSS(mgf=padding.MGF1(hashes.SHA256()), salt_length=s_len)
+    tests = []
+
+    def add(comment, msg, sig, result):
+        # Every label is cross-checked against the `cryptography` verifier.
+        try:
+            key.public_key().verify(sig, msg, pss, hashes.SHA256())
+            assert result == "valid", comment
+        except InvalidSignature:
+            assert result == "invalid", comment
+        tests.append({
+            "tcId": len(tests) + 1,
+            "comment": comment,
+            "msg": msg.hex(),
+            "sig": sig.hex(),
+            "result": result,
+        })
+
+    messages = [b"", b"abc", b"\x00" * 64] + [
+        rng.randbytes(rng.randrange(1, 1000)) for _ in range(4)
+    ]
+    for msg in messages:
+        add("", msg, key.sign(msg, pss, hashes.SHA256()), "valid")
+
+    msg = b"Test"
+    sig = key.sign(msg, pss, hashes.SHA256())
+    add("modified message", b"Tesu", sig, "invalid")
+    for i in [0, k // 2, k - 1]:
+        bad = bytearray(sig)
+        bad[i] ^= 1 << rng.randrange(8)
+        add(f"bit flip in byte {i}", msg, bytes(bad), "invalid")
+    add("leading zero byte prepended", msg, b"\x00" + sig, "invalid")
+    add("last byte dropped", msg, sig[:-1], "invalid")
+    add("signature is n", msg, n.to_bytes(k), "invalid")
+    add("signature is s + n", msg, (int.from_bytes(sig) + n).to_bytes(k + 1), "invalid")
+    add("signature is 0", msg, bytes(k), "invalid")
+    add("signature is 1", msg, (1).to_bytes(k), "invalid")
+
+    salt = rng.randbytes(s_len)
+    add("raw encoding", msg, raw_sign(key, encode(msg, salt, em_bits)), "valid")
+    for flaw in ["hash", "ps", "separator", "top-bit", "trailer"]:
+        em = encode(msg, salt, em_bits, flaw)
+        add(f"malformed encoding: {flaw}", msg, raw_sign(key, em), "invalid")
+    for other in [s_len - 1, s_len + 1]:
+        if other >= 0:
+            em = encode(msg, rng.randbytes(other), em_bits)
+            add(f"salt length {other}", msg, raw_sign(key, em), "invalid")
+
+    return {
+        "keySize": bits,
+        "sLen": s_len,
+        "n": f"{n:x}",
+        "e": f"{numbers.public_numbers.e:x}",
+        "d": f"{numbers.d:x}",
+        "p": f"{numbers.p:x}",
+        "q": f"{numbers.q:x}",
+        "dp": f"{numbers.dmp1:x}",
+        "dq": f"{numbers.dmq1:x}",
+        "qinv": f"{numbers.iqmp:x}",
+        "tests": tests,
+    }
+
+
+def main():
+    rng = random.Random(2025)
+    groups = [group(rng, bits, s_len) for bits, s_len in GROUPS]
+    count = sum(len(g["tests"]) for g in groups)
+    with open("sample/pss_vectors.json", "w") as f:
+        json.dump({
+            "algorithm": "RSASSA-PSS",
+            "sha": "SHA-256",
+            "mgf": "MGF1",
+            "mgfSha": "SHA-256",
+            "numberOfTests": count,
+            "testGroups": groups,
+        }, f, indent=2)
+        f.write("\n")
+    print(f"wrote {count} tests")
+
+
+if __name__ == "__main__":
+    main()
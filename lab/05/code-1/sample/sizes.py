@@ -0,0 +1,100 @@
+# Checks that code-1 (encrypt) and code-2 (decrypt) handle 1024 through
+# 8192-bit keys: code-1 takes the size from the input header, code-2 from the
+# input length. Ciphertexts are cross-checked with the `cryptography` OAEP
+# implementation in both directions.
+#
+# Run from the crate root with:
+#     python3 sample/sizes.py [rounds]
+
+import os
+import random
+import subprocess
+import sys
+
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import padding, rsa
+
+ENCRYPT = "target/release/code-1"
+DECRYPT = "../code-2/target/release/code-2"
+SIZES = [1024, 1536, 2048, 3072, 4096, 8192]
+
+
+def encrypt_input(key, header, message):
+    numbers = key.private_numbers()
+    k = key.key_size // 8
+    fields = [numbers.public_numbers.n, numbers.public_numbers.e, numbers.d]
+    body = b"".join(x.to_bytes(k) for x in fields)
+    return header + body + bytes([len(message)]) + message
+
+
+def decrypt_input(key, ciphertext):
+    numbers = key.private_numbers()
+    k = key.key_size // 8
+    fields = [
+        (numbers.p, k // 2),
+        (numbers.q, k // 2),
+        (numbers.public_numbers.n, k),
+        (numbers.d, k),
+        (numbers.dmp1, k // 2),
+        (numbers.dmq1, k // 2),
+        (numbers.iqmp, k // 2),
+    ]
+    return b"".join(x.to_bytes(w) for x, w in fields) + ciphertext
+
+
+def run(binary, stdin):
+    return subprocess.run([binary], input=stdin, capture_output=True)
+
+
+def main():
+    rounds = int(sys.argv[1]) if len(sys.argv) > 1 else 3
+    rng = random.Random(2025)
+    subprocess.run(["cargo", "build", "--release", "--quiet"], check=True)
+    subprocess.run(
+        ["cargo", "build", "--release", "--quiet"], cwd="../code-2", check=True
+    )
+    oaep = padding.OAEP(
+        mgf=padding.MGF1(algorithm=hashes.SHA256()),
+        algorithm=hashes.SHA256(),
+        label=None,
+    )
+
+    for bits in SIZES:
+        key = rsa.generate_private_key(public_exponent=65537, key_size=bits)
+        header = bits.to_bytes(4) + bytes(12)
+        max_len = min(bits // 8 - 66, 255)
+
+        for i in range(rounds):
+            message = rng.randbytes(max_len if i == 0 else rng.randrange(max_len + 1))
+
+            out = run(ENCRYPT, encrypt_input(key, header, message))
+            assert out.returncode == 0, out.stderr
+            assert len(out.stdout) == bits // 8
+            assert key.decrypt(out.stdout, oaep) == message
+
+            ciphertext = key.public_key().encrypt(message, oaep)
+            out = run(DECRYPT, decrypt_input(key, ciphertext))
+            assert out.returncode == 0 and out.stdout == message
+
+        # One byte too many for the modulus: rejected instead of panicking.
+        if max_len < 255:
+            out = run(ENCRYPT, encrypt_input(key, header, bytes(max_len + 1)))
+            assert out.returncode != 0 and out.stdout == b""
+
+        # A truncated key no longer matches any size: no output, exit 0.
+        ciphertext = key.public_key().encrypt(b"", oaep)
+        out = run(DECRYPT, decrypt_input(key, ciphertext)[:-1])
+        assert out.returncode == 0 and out.stdout == b""
+
+        print(f"{bits}-bit keys OK")
+
+    # Headers that do not announce a size keep the fixed 2048-bit layout.
+    key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    for header in [bytes(16), os.urandom(16)]:
+        out = run(ENCRYPT, encrypt_input(key, header, b"legacy"))
+        assert out.returncode == 0 and key.decrypt(out.stdout, oaep) == b"legacy"
+    print("legacy header OK")
+
+
+if __name__ == "__main__":
+    main()
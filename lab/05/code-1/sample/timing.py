@@ -0,0 +1,107 @@
+# Runs the `code-2 --timing` harness on a valid OAEP ciphertext against
+# ciphertexts that fail each decoding check in turn (first byte, lHash, PS,
+# missing separator) and against a second valid ciphertext as a baseline.
+# The harness exits non-zero when Welch's t-test finds a timing difference
+# in every one of its attempts, so a single noisy run does not fail it.
+#
+# Run from the crate root with:
+#     python3 sample/timing.py [samples]
+
+import hashlib
+import os
+import subprocess
+import sys
+import tempfile
+
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import padding, rsa
+
+DECRYPT = "../code-2/target/release/code-2"
+H_LEN = 32
+
+
+def mgf1(seed, length):
+    out = b""
+    for counter in range((length + H_LEN - 1) // H_LEN):
+        out += hashlib.sha256(seed + counter.to_bytes(4)).digest()
+    return out[:length]
+
+
+def xor(a, b):
+    return bytes(x ^ y for x, y in zip(a, b))
+
+
+def oaep_em(k, db, first=0):
+    # EME-OAEP encoding of an arbitrary (possibly malformed) DB.
+    seed = os.urandom(H_LEN)
+    masked_db = xor(db, mgf1(seed, k - H_LEN - 1))
+    masked_seed = xor(seed, mgf1(masked_db, H_LEN))
+    return bytes([first]) + masked_seed + masked_db
+
+
+def main():
+    samples = sys.argv[1] if len(sys.argv) > 1 else "200000"
+    subprocess.run(
+        ["cargo", "build", "--release", "--quiet"], cwd="../code-2", check=True
+    )
+
+    key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    numbers = key.private_numbers()
+    n, e = numbers.public_numbers.n, numbers.public_numbers.e
+    k = key.key_size // 8
+    oaep = padding.OAEP(
+        mgf=padding.MGF1(algorithm=hashes.SHA256()),
+        algorithm=hashes.SHA256(),
+        label=None,
+    )
+
+    msg = b"attack at dawn"
+    l_hash = hashlib.sha256(b"").digest()
+    ps = bytes(k - len(msg) - 2 * H_LEN - 2)
+    encrypt_raw = lambda em: pow(int.from_bytes(em), e, n).to_bytes(k)
+    cases = {
+        "valid (baseline)": key.public_key().encrypt(msg, oaep),
+        "first byte non-zero": encrypt_raw(oaep_em(k, l_hash + ps + b"\x01" + msg, first=1)),
+        "lHash mismatch": encrypt_raw(oaep_em(k, xor(l_hash, b"\x01") + ps + b"\x01" + msg)),
+        "non-zero PS byte": encrypt_raw(oaep_em(k, l_hash + ps[:-1] + b"\x02\x01" + msg)),
+        "no separator": encrypt_raw(oaep_em(k, l_hash + bytes(k - 2 * H_LEN - 1))),
+    }
+
+    with tempfile.TemporaryDirectory() as tmp:
+        dec_path = os.path.join(tmp, "decrypt.key")
+        with open(dec_path, "wb") as f:
+            for x, w in [
+                (numbers.p, k // 2), (numbers.q, k // 2), (n, k), (numbers.d, k),
+                (numbers.dmp1, k // 2), (numbers.dmq1, k // 2), (numbers.iqmp, k // 2),
+            ]:
+                f.write(x.to_bytes(w))
+
+        paths = {}
+        for name, c in cases.items():
+            paths[name] = os.path.join(tmp, f"{len(paths)}.bin")
+            with open(paths[name], "wb") as f:
+                f.write(c)
+
+        valid = os.path.join(tmp, "valid.bin")
+        with open(valid, "wb") as f:
+            f.write(key.public_key().encrypt(msg, oaep))
+
+        failed = []
+        for name, path in paths.items():
+            out = subprocess.run(
+                [DECRYPT, "--timing", dec_path, valid, path, samples],
+                capture_output=True, text=True,
+            )
+            assert out.returncode in (0, 1), out.stderr
+            for line in out.stdout.splitlines():
+                if line.startswith("attempt"):
+                    print(f"{name:>22}: {line}")
+            if out.returncode != 0:
+                failed.append(name)
+
+    assert not failed, f"timing difference for {failed}"
+    print("timing OK")
+
+
+if __name__ == "__main__":
+    main()
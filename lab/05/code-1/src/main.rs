@@ -9,10 +9,31 @@ use rand::{TryRngCore, rngs::OsRng};
 // ==========================================
 // 1. Constants
 // ==========================================
-const KEY_SIZE: usize = 256; // 2048 bits = 256 bytes
+const DEFAULT_KEY_SIZE: usize = 256; // 2048 bits = 256 bytes
+const MIN_KEY_SIZE: usize = 128; // 1024 bits = 128 bytes
+const MAX_KEY_SIZE: usize = 1024; // 8192 bits = 1024 bytes
 const HASH_LEN: usize = 32;  // SHA-256 output size
 
 fn main() -> io::Result<()> {
+    // `--keygen <bits> <encrypt-key> <decrypt-key>` writes a fresh key pair
+    // instead of encrypting stdin, `--verify <encrypt-key> <signature>
+    // [--salt-len N | --pkcs1]` checks an RSASSA-PSS (or RSASSA-PKCS1-v1_5)
+    // signature over stdin, and `--pkcs1` encrypts with RSAES-PKCS1-v1_5
+    // instead of OAEP.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let pkcs1 = match args.first().map(String::as_str) {
+        Some("--keygen") => return write_key_pair(&args[1..]),
+        Some("--verify") => return verify_stdin(&args[1..]),
+        Some("--pkcs1") if args.len() == 1 => true,
+        None => false,
+        _ => {
+            eprintln!("usage: code-1 [--pkcs1] < key || length || message");
+            eprintln!("       code-1 --keygen <2048|3072|4096> <encrypt-key> <decrypt-key>");
+            eprintln!("       code-1 --verify <encrypt-key> <signature> [--salt-len N | --pkcs1] < message");
+            std::process::exit(2);
+        }
+    };
+
     // Lock stdin.
     let stdin = io::stdin();
     let mut handle = stdin.lock();
@@ -21,23 +42,24 @@ fn main() -> io::Result<()> {
     // 2. Input Parsing (Streaming)
     // ==========================================
     
-    // 1. Ignore first 16 bytes
-    let mut _discard_16 = [0u8; 16];
-    handle.read_exact(&mut _discard_16)?;
+    // 1. Read the 16-byte header and infer the key size from it
+    let mut header = [0u8; 16];
+    handle.read_exact(&mut header)?;
+    let key_size = key_size_from_header(&header);
 
-    // 2. Read N (256 bytes)
-    let mut n_bytes = [0u8; KEY_SIZE];
+    // 2. Read N (key_size bytes)
+    let mut n_bytes = vec![0u8; key_size];
     handle.read_exact(&mut n_bytes)?;
     let n = BigUint::from_be_bytes(&n_bytes);
 
-    // 3. Read E (256 bytes)
-    let mut e_bytes = [0u8; KEY_SIZE];
+    // 3. Read E (key_size bytes)
+    let mut e_bytes = vec![0u8; key_size];
     handle.read_exact(&mut e_bytes)?;
     let e = BigUint::from_be_bytes(&e_bytes);
 
-    // 4. Ignore next 256 bytes (likely D, not needed for encryption)
-    let mut _discard_256 = [0u8; KEY_SIZE];
-    handle.read_exact(&mut _discard_256)?;
+    // 4. Ignore next key_size bytes (likely D, not needed for encryption)
+    let mut _discard_d = vec![0u8; key_size];
+    handle.read_exact(&mut _discard_d)?;
 
     // 5. Read Message Length (1 byte)
     let mut m_len_buf = [0u8; 1];
@@ -49,52 +71,83 @@ fn main() -> io::Result<()> {
     handle.read_exact(&mut message)?;
 
     // ==========================================
-    // 3. OAEP Padding
+    // 3. Padding (OAEP, or PKCS#1 v1.5 with --pkcs1)
+    // ==========================================
+    let em = if pkcs1 {
+        pkcs1_encrypt_encode(&message, key_size)?
+    } else {
+        oaep_encode(&message, key_size)?
+    };
+
+    // ==========================================
+    // 4. RSA Encryption with Montgomery Reduction
     // ==========================================
+    let m_int = BigUint::from_be_bytes(&em);
+    
+    // Use the optimized modular exponentiation
+    let c_int = m_int.modpow(&e, &n);
+
+    // Output key_size bytes
+    let c_bytes = c_int.to_bytes_be(key_size);
+    io::stdout().write_all(&c_bytes)?;
+
+    Ok(())
+}
+
+// The header may carry the modulus size in bits as a big-endian u32 followed
+// by 12 zero bytes, as written by `--keygen`. Any other header is taken to be
+// the original fixed 2048-bit layout.
+fn key_size_from_header(header: &[u8; 16]) -> usize {
+    let bits = BigEndian::read_u32(&header[..4]) as usize;
+    let key_size = bits / 8;
+    if header[4..].iter().all(|&b| b == 0)
+        && bits.is_multiple_of(8)
+        && (MIN_KEY_SIZE..=MAX_KEY_SIZE).contains(&key_size)
+    {
+        key_size
+    } else {
+        DEFAULT_KEY_SIZE
+    }
+}
+
+// EME-OAEP encoding with SHA-256, MGF1-SHA-256 and an empty label.
+fn oaep_encode(message: &[u8], key_size: usize) -> io::Result<Vec<u8>> {
     // L is empty, lHash = Hash(L)
     let l_hash = Sha256::digest(&[]);
 
     // PS (Padding String) - Zeros
-    let ps_len = KEY_SIZE - m_len - 2 * HASH_LEN - 2;
+    // A 1024-bit key leaves room for at most 62 bytes, less than the 255 a
+    // length byte can announce.
+    let ps_len = key_size
+        .checked_sub(message.len() + 2 * HASH_LEN + 2)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "message too long"))?;
     let ps = vec![0u8; ps_len];
 
     // DB = lHash || PS || 0x01 || M
-    let mut db = Vec::with_capacity(KEY_SIZE - HASH_LEN - 1);
+    let mut db = Vec::with_capacity(key_size - HASH_LEN - 1);
     db.extend_from_slice(&l_hash);
     db.extend_from_slice(&ps);
     db.push(0x01);
-    db.extend_from_slice(&message);
+    db.extend_from_slice(message);
 
     // Generate random Seed using rand crate (CSPRNG)
     let mut seed = [0u8; HASH_LEN];
     OsRng.try_fill_bytes(&mut seed).unwrap();
 
     // MGF1 Masking
-    let db_mask = mgf1(&seed, KEY_SIZE - HASH_LEN - 1);
+    let db_mask = mgf1(&seed, key_size - HASH_LEN - 1);
     let masked_db: Vec<u8> = db.iter().zip(db_mask.iter()).map(|(a, b)| a ^ b).collect();
 
     let seed_mask = mgf1(&masked_db, HASH_LEN);
     let masked_seed: Vec<u8> = seed.iter().zip(seed_mask.iter()).map(|(a, b)| a ^ b).collect();
 
     // EM = 0x00 || maskedSeed || maskedDB
-    let mut em = Vec::with_capacity(KEY_SIZE);
+    let mut em = Vec::with_capacity(key_size);
     em.push(0x00);
     em.extend_from_slice(&masked_seed);
     em.extend_from_slice(&masked_db);
 
-    // ==========================================
-    // 4. RSA Encryption with Montgomery Reduction
-    // ==========================================
-    let m_int = BigUint::from_be_bytes(&em);
-    
-    // Use the optimized modular exponentiation
-    let c_int = m_int.modpow(&e, &n);
-
-    // Output 256 bytes
-    let c_bytes = c_int.to_bytes_be(KEY_SIZE);
-    io::stdout().write_all(&c_bytes)?;
-
-    Ok(())
+    Ok(em)
 }
 
 fn mgf1(seed: &[u8], len: usize) -> Vec<u8> {
@@ -115,16 +168,13 @@ fn mgf1(seed: &[u8], len: usize) -> Vec<u8> {
 // 5. BigUint Implementation (Montgomery Optimized)
 // ==========================================
 
-// We need 2048 bits. 64 bits * 32 = 2048.
+// Keys go up to 8192 bits = 128 u64 limbs.
 // To handle overflow during multiplication before reduction, we usually need 2x size.
-// 256 bytes = 32 u64 limbs.
-// For Montgomery mul, we keep the `LIMBS` large enough to hold intermediate product.
-// 2048 bits / 64 = 32 limbs.
-// Product is 4096 bits = 64 limbs.
-// So LIMBS = 66 gives us a safe margin for carries.
-const LIMBS: usize = 66; 
-
-#[derive(Debug, Clone, Copy)]
+// Product is 16384 bits = 256 limbs.
+// So two extra limbs give us a safe margin for carries.
+const LIMBS: usize = 2 * MAX_KEY_SIZE / 8 + 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct BigUint {
     data: [u64; LIMBS],
 }
@@ -136,6 +186,12 @@ impl BigUint {
         }
     }
 
+    fn from_u64(value: u64) -> Self {
+        let mut res = Self::new();
+        res.data[0] = value;
+        res
+    }
+
     fn from_be_bytes(bytes: &[u8]) -> Self {
         let mut bytes = bytes.to_vec();
         bytes.reverse();
@@ -143,7 +199,7 @@ impl BigUint {
     }
 
     fn from_le_bytes(bytes: &[u8]) -> Self {
-        // Ensure we don't buffer overflow if input is huge, though logic says 256 bytes max
+        // Ensure we don't buffer overflow if input is huge, though logic says 1024 bytes max
         let mut data = [0u64; LIMBS];
         bytes.chunks(8).enumerate().for_each(|(i, chunk)| {
             if i < LIMBS {
@@ -158,9 +214,9 @@ impl BigUint {
         }
     }
 
-    fn to_bytes_be(&self, width: usize) -> Vec<u8> {
+    fn to_bytes_be(self, width: usize) -> Vec<u8> {
         let mut res = vec![0u8; width];
-        let mut temp = *self;
+        let mut temp = self;
         for i in (0..width).rev() {
             res[i] = (temp.data[0] & 0xFF) as u8;
             temp.shr_8();
@@ -176,8 +232,8 @@ impl BigUint {
         // n_prime = -N^(-1) mod 2^64
         let n_prime = Self::compute_n_prime(modulus.data[0]);
         
-        // R = 2^(64 * 32) for 2048 bit modulus (assuming modulus fits in 32 limbs)
-        // We need R > N. Since N is 2048 bits, let's define R based on limbs count corresponding to N.
+        // e.g. R = 2^(64 * 32) for a 2048 bit modulus (which fits in 32 limbs)
+        // We need R > N, so let's define R based on limbs count corresponding to N.
         // A simpler approach for general code:
         // Find number of limbs used by modulus.
         let n_limbs = modulus.limbs_used();
@@ -186,29 +242,28 @@ impl BigUint {
         // Calculate R^2 mod N
         // R is represented by a 1 at bit index (64 * n_limbs).
         // We construct R^2 by shifting or using BigUint arithmetic, then taking remainder.
-        let mut r_sq = BigUint::new();
         // To be safe, we can compute 2^(2 * 64 * n_limbs) mod N.
         // Or simply: compute 2^(64 * n_limbs) % N, then square it mod N.
         
         // Construct R % N
         let mut r_val = BigUint::new();
         // Set bit at 64 * n_limbs
-        // Since our LIMBS is 66 and n_limbs is likely 32, we can handle this.
+        // LIMBS covers twice the largest supported modulus, so we can handle this.
         if n_limbs < LIMBS {
             r_val.data[n_limbs] = 1; 
         } else {
-            // Fallback/Error case, though for 2048 bits LIMBS=66 is fine
+            // Fallback/Error case, unreachable for moduli up to 8192 bits
              panic!("Modulus too large for configured LIMBS");
         }
         r_val = r_val.rem(modulus); // R mod N
 
         // Compute R^2 mod N
         // We can use standard mul_mod here just once to initialize.
-        r_sq = r_val.mul_mod(&r_val, modulus); 
+        let r_sq = r_val.mul_mod(&r_val, modulus);
 
         // 2. Convert Base to Montgomery Form: A_mont = A * R mod N
         // We can use mont_mul(A, R^2) -> A * R^2 * R^-1 = A * R
-        let mut x = self.mont_mul(&r_sq, modulus, n_prime, n_limbs);
+        let x = self.mont_mul(&r_sq, modulus, n_prime, n_limbs);
 
         // 3. Initialize result to 1 in Montgomery Form: 1 * R mod N
         // We already have R mod N in r_val.
@@ -432,6 +487,16 @@ impl BigUint {
         }
     }
 
+    fn add_assign(&mut self, other: &BigUint) {
+        let mut carry: u64 = 0;
+        for i in 0..LIMBS {
+            let (sum, c1) = self.data[i].overflowing_add(other.data[i]);
+            let (sum2, c2) = sum.overflowing_add(carry);
+            self.data[i] = sum2;
+            carry = (if c1 {1} else {0}) + (if c2 {1} else {0});
+        }
+    }
+
     fn shl_1(&mut self) {
         let mut carry = 0;
         for i in 0..LIMBS {
@@ -441,6 +506,49 @@ impl BigUint {
         }
     }
     
+    fn shr_1(&mut self) {
+        let mut carry = 0;
+        for i in (0..LIMBS).rev() {
+            let next_carry = self.data[i] << 63;
+            self.data[i] = (self.data[i] >> 1) | carry;
+            carry = next_carry;
+        }
+    }
+
+    // Number of significant bits (0 for zero).
+    fn bits(&self) -> usize {
+        for i in (0..LIMBS).rev() {
+            if self.data[i] != 0 {
+                return i * 64 + 64 - self.data[i].leading_zeros() as usize;
+            }
+        }
+        0
+    }
+
+    // Multiply by a single limb; the caller guarantees the result fits.
+    fn mul_small(&self, k: u64) -> BigUint {
+        let mut res = BigUint::new();
+        let mut carry: u128 = 0;
+        for i in 0..LIMBS {
+            let val = (self.data[i] as u128) * (k as u128) + carry;
+            res.data[i] = val as u64;
+            carry = val >> 64;
+        }
+        res
+    }
+
+    // Long division by a single limb, returning (quotient, remainder).
+    fn div_rem_small(&self, k: u64) -> (BigUint, u64) {
+        let mut quotient = BigUint::new();
+        let mut rem: u128 = 0;
+        for i in (0..LIMBS).rev() {
+            let cur = (rem << 64) | self.data[i] as u128;
+            quotient.data[i] = (cur / k as u128) as u64;
+            rem = cur % k as u128;
+        }
+        (quotient, rem as u64)
+    }
+
     fn shr_8(&mut self) {
         let mut carry = 0;
         for i in (0..LIMBS).rev() {
@@ -527,8 +635,8 @@ impl Sha256 {
 
     fn transform(&mut self) {
         let mut m = [0u32; 64];
-        for i in 0..16 {
-            m[i] = BigEndian::read_u32(&self.data[i * 4..(i + 1) * 4]);
+        for (word, chunk) in m.iter_mut().zip(self.data.chunks(4)) {
+            *word = BigEndian::read_u32(chunk);
         }
         for i in 16..64 {
             let s0 = m[i - 15].rotate_right(7) ^ m[i - 15].rotate_right(18) ^ (m[i - 15] >> 3);
@@ -584,3 +692,432 @@ impl Sha256 {
         self.state[7] = self.state[7].wrapping_add(h);
     }
 }
+
+// ==========================================
+// 7. Key Generation
+// ==========================================
+
+const PUBLIC_EXPONENT: u64 = 65537;
+// Odd primes below this bound are used to sieve candidates before Miller-Rabin.
+const SIEVE_LIMIT: usize = 1 << 14;
+// Random candidates that survive one round are almost always prime; the
+// remaining rounds bound the error for adversarial inputs by 4^-MR_ROUNDS.
+const MR_ROUNDS: usize = 40;
+// Sizes offered by --keygen. Each must be a layout that the encrypt path here
+// and the decrypt path in code-2 both read: within MIN_KEY_SIZE..=MAX_KEY_SIZE
+// bytes, with a whole number of bytes for each prime.
+const KEYGEN_BITS: [usize; 3] = [2048, 3072, 4096];
+const _: () = {
+    let mut i = 0;
+    while i < KEYGEN_BITS.len() {
+        let k = KEYGEN_BITS[i] / 8;
+        assert!(KEYGEN_BITS[i].is_multiple_of(16) && k >= MIN_KEY_SIZE && k <= MAX_KEY_SIZE);
+        i += 1;
+    }
+};
+
+struct RsaKey {
+    n: BigUint,
+    e: BigUint,
+    d: BigUint,
+    p: BigUint,
+    q: BigUint,
+    dp: BigUint,
+    dq: BigUint,
+    qinv: BigUint,
+}
+
+fn write_key_pair(args: &[String]) -> io::Result<()> {
+    let usage = || -> ! {
+        eprintln!("usage: code-1 --keygen <2048|3072|4096> <encrypt-key> <decrypt-key>");
+        std::process::exit(2);
+    };
+    let [bits, encrypt_path, decrypt_path] = args else { usage() };
+    let bits: usize = match bits.parse() {
+        Ok(b) if KEYGEN_BITS.contains(&b) => b,
+        _ => usage(),
+    };
+    let key = generate_key(bits);
+    let k = bits / 8;
+
+    // Encrypt input prefix: header (bit length) || N || E || D.
+    // The caller appends the message length byte and the message.
+    let mut public = Vec::with_capacity(16 + 3 * k);
+    let mut header = [0u8; 16];
+    BigEndian::write_u32(&mut header[..4], bits as u32);
+    public.extend_from_slice(&header);
+    for x in [key.n, key.e, key.d] {
+        public.extend_from_slice(&x.to_bytes_be(k));
+    }
+    std::fs::write(encrypt_path, public)?;
+
+    // Decrypt input prefix: p || q || N || D || dP || dQ || qInv.
+    // The caller appends the ciphertext.
+    let mut private = Vec::with_capacity(k * 11 / 2);
+    for (x, width) in [
+        (key.p, k / 2),
+        (key.q, k / 2),
+        (key.n, k),
+        (key.d, k),
+        (key.dp, k / 2),
+        (key.dq, k / 2),
+        (key.qinv, k / 2),
+    ] {
+        private.extend_from_slice(&x.to_bytes_be(width));
+    }
+    std::fs::write(decrypt_path, private)?;
+
+    Ok(())
+}
+
+fn generate_key(bits: usize) -> RsaKey {
+    let e = BigUint::from_u64(PUBLIC_EXPONENT);
+    let small_primes = small_primes(SIEVE_LIMIT);
+
+    loop {
+        let p = generate_prime(bits / 2, &small_primes);
+        let q = generate_prime(bits / 2, &small_primes);
+
+        // FIPS 186-5 A.1.3: |p - q| must exceed 2^(nlen/2 - 100), otherwise
+        // Fermat factorisation recovers them from N.
+        let (hi, lo) = if p.ge(&q) { (p, q) } else { (q, p) };
+        let mut diff = hi;
+        diff.sub_assign(&lo);
+        if diff.bits() <= bits / 2 - 100 {
+            continue;
+        }
+
+        let n = p.mul(&q);
+        if n.bits() != bits {
+            continue;
+        }
+
+        // p and q are odd, so clearing bit 0 subtracts one without borrowing.
+        let mut p1 = p;
+        p1.data[0] &= !1;
+        let mut q1 = q;
+        q1.data[0] &= !1;
+        let phi = p1.mul(&q1);
+
+        // e is prime and does not divide p - 1 or q - 1, so it is invertible
+        // mod phi. With k = -phi^(-1) mod e, (k * phi + 1) is divisible by e
+        // and the quotient is the inverse we want.
+        let phi_mod_e = phi.div_rem_small(PUBLIC_EXPONENT).1;
+        let phi_inv = pow_mod_small(phi_mod_e, PUBLIC_EXPONENT - 2, PUBLIC_EXPONENT);
+        let k = (PUBLIC_EXPONENT - phi_inv) % PUBLIC_EXPONENT;
+        let mut t = phi.mul_small(k);
+        t.add_assign(&BigUint::from_u64(1));
+        let (d, r) = t.div_rem_small(PUBLIC_EXPONENT);
+        debug_assert_eq!(r, 0);
+
+        let dp = d.rem(&p1);
+        let dq = d.rem(&q1);
+
+        // p is prime, so q^(p - 2) = q^(-1) mod p (Fermat).
+        let mut p2 = p;
+        p2.sub_assign(&BigUint::from_u64(2));
+        let qinv = q.modpow(&p2, &p);
+
+        return RsaKey { n, e, d, p, q, dp, dq, qinv };
+    }
+}
+
+// Random prime with exactly `bits` bits and the top two bits set, so that the
+// product of two such primes has exactly 2 * bits bits.
+fn generate_prime(bits: usize, small_primes: &[u64]) -> BigUint {
+    loop {
+        let mut bytes = vec![0u8; bits / 8];
+        OsRng.try_fill_bytes(&mut bytes).unwrap();
+        bytes[0] |= 0xC0;
+        bytes[bits / 8 - 1] |= 1;
+        let base = BigUint::from_be_bytes(&bytes);
+
+        // Walk base, base + 2, ... keeping the residues mod each small prime
+        // so that most composites are rejected without any big arithmetic.
+        let residues: Vec<u64> = small_primes.iter().map(|&sp| base.div_rem_small(sp).1).collect();
+        let e_residue = base.div_rem_small(PUBLIC_EXPONENT).1;
+
+        let mut delta = 0u64;
+        while delta < 1 << 16 {
+            let divisible = small_primes
+                .iter()
+                .zip(&residues)
+                .any(|(&sp, &r)| (r + delta).is_multiple_of(sp));
+            // gcd(e, p - 1) must be 1 for e to be invertible.
+            let bad_e = (e_residue + delta) % PUBLIC_EXPONENT == 1;
+
+            if !divisible && !bad_e {
+                let mut candidate = base;
+                candidate.add_assign(&BigUint::from_u64(delta));
+                if candidate.bits() == bits && is_probable_prime(&candidate, bits) {
+                    return candidate;
+                }
+            }
+            delta += 2;
+        }
+    }
+}
+
+fn is_probable_prime(n: &BigUint, bits: usize) -> bool {
+    let one = BigUint::from_u64(1);
+    let mut n1 = *n;
+    n1.sub_assign(&one);
+
+    // n - 1 = 2^s * r with r odd.
+    let mut r = n1;
+    let mut s = 0;
+    while r.data[0] & 1 == 0 {
+        r.shr_1();
+        s += 1;
+    }
+
+    'witness: for _ in 0..MR_ROUNDS {
+        // Random base in [2, 2^(bits - 1)), which is below n - 1.
+        let a = loop {
+            let mut bytes = vec![0u8; bits / 8];
+            OsRng.try_fill_bytes(&mut bytes).unwrap();
+            bytes[0] &= 0x7F;
+            let a = BigUint::from_be_bytes(&bytes);
+            if a.bits() > 1 {
+                break a;
+            }
+        };
+
+        let mut x = a.modpow(&r, n);
+        if x == one || x == n1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.mul_mod(&x, n);
+            if x == n1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+// Odd primes up to `limit` (sieve of Eratosthenes).
+fn small_primes(limit: usize) -> Vec<u64> {
+    let mut composite = vec![false; limit];
+    let mut primes = Vec::new();
+    for i in (3..limit).step_by(2) {
+        if composite[i] {
+            continue;
+        }
+        primes.push(i as u64);
+        for j in (i * i..limit).step_by(i) {
+            composite[j] = true;
+        }
+    }
+    primes
+}
+
+fn pow_mod_small(mut base: u64, mut exp: u64, modulus: u64) -> u64 {
+    let mut res = 1u64;
+    base %= modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = res * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    res
+}
+
+// ==========================================
+// 8. RSASSA-PSS Verification (RFC 8017 8.1.2)
+// ==========================================
+
+fn verify_stdin(args: &[String]) -> io::Result<()> {
+    let usage = || -> ! {
+        eprintln!("usage: code-1 --verify <encrypt-key> <signature> [--salt-len N | --pkcs1] < message");
+        std::process::exit(2);
+    };
+    let (key_path, signature_path, salt_len) = match args {
+        [key, sig] => (key, sig, Some(HASH_LEN)),
+        [key, sig, flag] if flag == "--pkcs1" => (key, sig, None),
+        [key, sig, flag, len] if flag == "--salt-len" => {
+            (key, sig, Some(len.parse().unwrap_or_else(|_| usage())))
+        }
+        _ => usage(),
+    };
+
+    // Encrypt key: header || N || E || D
+    let malformed = || io::Error::new(io::ErrorKind::InvalidData, "malformed encrypt key");
+    let key = std::fs::read(key_path)?;
+    let header: &[u8; 16] = key.get(..16).and_then(|h| h.try_into().ok()).ok_or_else(malformed)?;
+    let key_size = key_size_from_header(header);
+    if key.len() != 16 + 3 * key_size {
+        return Err(malformed());
+    }
+    let n = BigUint::from_be_bytes(&key[16..16 + key_size]);
+    let e = BigUint::from_be_bytes(&key[16 + key_size..16 + 2 * key_size]);
+    if n.is_zero() {
+        return Err(malformed());
+    }
+    let signature = std::fs::read(signature_path)?;
+
+    // mHash = Hash(M), streaming the message from stdin
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 8192];
+    let mut handle = io::stdin().lock();
+    loop {
+        let read = handle.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+    let m_hash = hasher.finalize();
+
+    let valid = match salt_len {
+        Some(salt_len) => pss_verify(&n, &e, &m_hash, &signature, salt_len),
+        None => pkcs1_verify(&n, &e, &m_hash, &signature),
+    };
+    if valid {
+        println!("Verified OK");
+        Ok(())
+    } else {
+        eprintln!("Verification failure");
+        std::process::exit(1);
+    }
+}
+
+// Length check and RSAVP1 shared by both signature schemes: m = s^e mod n.
+fn rsavp1(n: &BigUint, e: &BigUint, signature: &[u8]) -> Option<BigUint> {
+    // 1. The signature must be exactly k bytes
+    if signature.len() != n.bits().div_ceil(8) {
+        return None;
+    }
+
+    // 2. s must be below n
+    let s = BigUint::from_be_bytes(signature);
+    if s.ge(n) {
+        return None;
+    }
+    Some(s.modpow(e, n))
+}
+
+fn pss_verify(n: &BigUint, e: &BigUint, m_hash: &[u8], signature: &[u8], salt_len: usize) -> bool {
+    // 1-2. Length check and RSAVP1
+    let Some(m) = rsavp1(n, e, signature) else {
+        return false;
+    };
+
+    // EM = I2OSP(m, emLen) with emBits = modBits - 1; fails if m does not fit
+    let em_bits = n.bits() - 1;
+    let em_len = em_bits.div_ceil(8);
+    if m.bits() > 8 * em_len {
+        return false;
+    }
+    let em = m.to_bytes_be(em_len);
+
+    // 3. EMSA-PSS-VERIFY
+    pss_decode(m_hash, &em, em_bits, salt_len)
+}
+
+// EMSA-PSS-VERIFY with SHA-256 and MGF1-SHA-256.
+fn pss_decode(m_hash: &[u8], em: &[u8], em_bits: usize, salt_len: usize) -> bool {
+    let em_len = em.len();
+    // salt_len comes from the command line; don't let the sum wrap around
+    match HASH_LEN.checked_add(salt_len).and_then(|v| v.checked_add(2)) {
+        Some(min_len) if em_len >= min_len => {}
+        _ => return false,
+    }
+
+    // EM = maskedDB || H || 0xbc
+    if em[em_len - 1] != 0xbc {
+        return false;
+    }
+    let (masked_db, h) = em[..em_len - 1].split_at(em_len - HASH_LEN - 1);
+
+    // The leftmost 8 * emLen - emBits bits of maskedDB must be zero
+    let top_mask = 0xFFu8 >> (8 * em_len - em_bits);
+    if masked_db[0] & !top_mask != 0 {
+        return false;
+    }
+
+    let db_mask = mgf1(h, em_len - HASH_LEN - 1);
+    let mut db: Vec<u8> = masked_db.iter().zip(db_mask.iter()).map(|(a, b)| a ^ b).collect();
+    db[0] &= top_mask;
+
+    // DB = PS || 0x01 || salt
+    let ps_len = em_len - HASH_LEN - salt_len - 2;
+    if db[..ps_len].iter().any(|&b| b != 0) || db[ps_len] != 0x01 {
+        return false;
+    }
+    let salt = &db[ps_len + 1..];
+
+    // H' = Hash(0x00 * 8 || mHash || salt) must equal H
+    let mut hasher = Sha256::new();
+    hasher.update(&[0u8; 8]);
+    hasher.update(m_hash);
+    hasher.update(salt);
+    hasher.finalize() == h
+}
+
+// ==========================================
+// 9. PKCS#1 v1.5 (RFC 8017 7.2 and 8.2)
+// ==========================================
+
+// DER encoding of DigestInfo { AlgorithmIdentifier { sha256, NULL }, OCTET STRING (32) }
+const SHA256_DIGEST_INFO: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01,
+    0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0x04, 0x20,
+];
+
+// EME-PKCS1-v1_5: EM = 0x00 || 0x02 || PS (random, non-zero) || 0x00 || M
+fn pkcs1_encrypt_encode(message: &[u8], key_size: usize) -> io::Result<Vec<u8>> {
+    // PS must be at least 8 bytes
+    let ps_len = key_size
+        .checked_sub(message.len() + 3)
+        .filter(|&len| len >= 8)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "message too long"))?;
+
+    let mut ps = vec![0u8; ps_len];
+    OsRng.try_fill_bytes(&mut ps).unwrap();
+    for b in ps.iter_mut() {
+        // Redraw zero bytes, which would end the padding early
+        while *b == 0 {
+            let mut byte = [0u8; 1];
+            OsRng.try_fill_bytes(&mut byte).unwrap();
+            *b = byte[0];
+        }
+    }
+
+    let mut em = Vec::with_capacity(key_size);
+    em.extend_from_slice(&[0x00, 0x02]);
+    em.extend_from_slice(&ps);
+    em.push(0x00);
+    em.extend_from_slice(message);
+    Ok(em)
+}
+
+// EMSA-PKCS1-v1_5: EM = 0x00 || 0x01 || 0xff... || 0x00 || DigestInfo || H
+fn pkcs1_sign_encode(m_hash: &[u8], em_len: usize) -> Vec<u8> {
+    let t_len = SHA256_DIGEST_INFO.len() + HASH_LEN;
+    let mut em = vec![0x00, 0x01];
+    em.resize(em_len - t_len - 1, 0xFF);
+    em.push(0x00);
+    em.extend_from_slice(&SHA256_DIGEST_INFO);
+    em.extend_from_slice(m_hash);
+    em
+}
+
+// RSASSA-PKCS1-v1_5 verification by re-encoding (RFC 8017 8.2.2 step 3):
+// the recovered EM must equal the expected encoding byte for byte, so there
+// is no ASN.1 parsing for a Bleichenbacher'06 forgery (short padding, garbage
+// after the digest, extra DigestInfo fields) to slip through.
+fn pkcs1_verify(n: &BigUint, e: &BigUint, m_hash: &[u8], signature: &[u8]) -> bool {
+    let key_size = n.bits().div_ceil(8);
+    if key_size < SHA256_DIGEST_INFO.len() + HASH_LEN + 11 {
+        return false;
+    }
+    let Some(m) = rsavp1(n, e, signature) else {
+        return false;
+    };
+    m.to_bytes_be(key_size) == pkcs1_sign_encode(m_hash, key_size)
+}
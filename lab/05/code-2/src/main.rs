@@ -1,21 +1,44 @@
 // Standard Input/Output.
 use std::io;
 use std::io::{Read, Write};
+// Timing harness.
+use std::hint::black_box;
+use std::time::Instant;
 // Process bytes.
 use byteorder::{BigEndian, ByteOrder};
+// CSPRNG.
+use rand::{TryRngCore, rngs::OsRng};
 
 // ==========================================
 // 1. Constants
 // ==========================================
-const KEY_SIZE: usize = 256; // 2048 bits = 256 bytes
-const PRIME_SIZE: usize = 128; // 1024 bits = 128 bytes
+const MIN_KEY_SIZE: usize = 128; // 1024 bits = 128 bytes
+const MAX_KEY_SIZE: usize = 1024; // 8192 bits = 1024 bytes
 const HASH_LEN: usize = 32;  // SHA-256 output size
 
 fn main() -> io::Result<()> {
+    // `--sign <decrypt-key> [--salt-len N | --pkcs1]` signs stdin with
+    // RSASSA-PSS (or RSASSA-PKCS1-v1_5) instead of decrypting it,
+    // `--timing ...` runs the OAEP decoding timing harness, and `--pkcs1`
+    // decrypts RSAES-PKCS1-v1_5 instead of OAEP.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let pkcs1 = match args.first().map(String::as_str) {
+        Some("--sign") => return sign_stdin(&args[1..]),
+        Some("--timing") => return timing_harness(&args[1..]),
+        Some("--pkcs1") if args.len() == 1 => true,
+        None => false,
+        _ => {
+            eprintln!("usage: code-2 [--pkcs1] < key || ciphertext");
+            eprintln!("       code-2 --sign <decrypt-key> [--salt-len N | --pkcs1] < message");
+            eprintln!("       code-2 --timing <decrypt-key> <ciphertext-a> <ciphertext-b> [samples]");
+            std::process::exit(2);
+        }
+    };
+
     // We wrap the logic in a helper function.
     // If it returns None (any error/invalid input), main still returns Ok(()).
     // This ensures Exit Code 0 and "prints nothing" on failure.
-    if let Some(message) = try_decrypt() {
+    if let Some(message) = try_decrypt(pkcs1) {
         let stdout = io::stdout();
         let mut handle = stdout.lock();
         handle.write_all(&message)?;
@@ -23,71 +46,166 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
-fn try_decrypt() -> Option<Vec<u8>> {
-    let stdin = io::stdin();
-    let mut handle = stdin.lock();
+// CRT form of the private key, as laid out at the start of the decrypt input.
+struct PrivateKey {
+    key_size: usize,
+    p: BigUint,
+    q: BigUint,
+    n: BigUint,
+    d: BigUint,
+    dp: BigUint,
+    dq: BigUint,
+    qinv: BigUint,
+}
 
-    // Helper: read exact bytes, return None if EOF or error
-    let mut read_exact = |buf: &mut [u8]| -> Option<()> {
-        handle.read_exact(buf).ok()
-    };
+fn try_decrypt(pkcs1: bool) -> Option<Vec<u8>> {
+    // Read the whole input: it carries no header, so the key size comes from
+    // its length. p, q, dP, dQ and qInv take k/2 bytes each and n, d and c
+    // take k bytes each, 11k/2 bytes in total.
+    let mut input = Vec::new();
+    io::stdin().lock().read_to_end(&mut input).ok()?;
+    if !(input.len() * 2).is_multiple_of(11) { return None; }
+    let key_size = input.len() * 2 / 11;
 
     // ==========================================
     // 2. Input Parsing
     // ==========================================
 
+    // 1-7. Read p, q, n, d, dP, dQ, qInv
+    let (key_bytes, c_bytes) = input.split_at(input.len() - key_size);
+    let key = parse_private_key(key_bytes)?;
+
+    // 8. Read Ciphertext
+    let c = BigUint::from_be_bytes(c_bytes);
+
+    // ==========================================
+    // 3. CRT Decryption
+    // ==========================================
+    let m = crt_exp(&key, &c);
+
+    // EM (Encoded Message)
+    let em = m.to_bytes_be(key.key_size);
+
+    // RSAES-PKCS1-v1_5 never fails: bad padding yields a synthetic message
+    if pkcs1 { return Some(pkcs1_decode(&key, c_bytes, &em)); }
+
+    // ==========================================
+    // 4. OAEP Decoding
+    // ==========================================
+    oaep_decode(&em)
+}
+
+// EME-OAEP decoding in constant time. Every check is folded into one mask and
+// there is a single failure exit at the end, so timing does not reveal which
+// check failed; telling "first byte non-zero" apart from the rest is exactly
+// the oracle Manger's attack needs.
+fn oaep_decode(em: &[u8]) -> Option<Vec<u8>> {
+    let mut db = [0u8; MAX_KEY_SIZE];
+    let db = &mut db[..em.len() - HASH_LEN - 1];
+    let (good, separator_idx) = oaep_check(em, db);
+
+    // Single failure exit
+    if good == 0 { return None; }
+
+    // 6. Extract Message
+    Some(db[separator_idx + 1..].to_vec())
+}
+
+// Steps 1-5 of the decoding without any data-dependent branch or heap
+// allocation. Recovers DB into `db` (k - hLen - 1 bytes) and returns the
+// validity mask and the index of the 0x01 separator in DB.
+fn oaep_check(em: &[u8], db: &mut [u8]) -> (usize, usize) {
+    // 1. First byte must be 0x00
+    let mut good = ct_is_zero(em[0] as usize);
+
+    let masked_seed = &em[1..1 + HASH_LEN];
+    let masked_db = &em[1 + HASH_LEN..];
+
+    // 2. Recover Seed
+    let mut seed = [0u8; HASH_LEN];
+    seed.copy_from_slice(masked_seed);
+    mgf1_xor(masked_db, &mut seed);
+
+    // 3. Recover DB
+    db.copy_from_slice(masked_db);
+    mgf1_xor(&seed, db);
+
+    // 4. Verify lHash, accumulating differences instead of stopping early.
+    // black_box keeps the compiler from branching on the accumulated byte.
+    let l_hash_expected = Sha256::digest(&[]);
+    let l_hash_diff = db[..HASH_LEN]
+        .iter()
+        .zip(l_hash_expected.iter())
+        .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+    good &= ct_is_zero(black_box(l_hash_diff) as usize);
+
+    // 5. Find the first 0x01 after PS, scanning the whole of DB; any other
+    // non-zero byte before it invalidates the padding
+    let mut found = 0;
+    let mut separator_idx = 0;
+    let mut bad_ps = 0;
+    for (i, &b) in db.iter().enumerate().skip(HASH_LEN) {
+        let is_one = ct_eq(b as usize, 0x01);
+        let is_zero = ct_is_zero(b as usize);
+        separator_idx = ct_select(!found & is_one, i, separator_idx);
+        bad_ps |= !found & !is_one & !is_zero;
+        found |= is_one;
+    }
+    good &= found & !bad_ps;
+
+    (good, separator_idx)
+}
+
+// Parse p || q || n || d || dP || dQ || qInv, 9k/2 bytes for a k-byte modulus.
+fn parse_private_key(bytes: &[u8]) -> Option<PrivateKey> {
+    if !(bytes.len() * 2).is_multiple_of(9) { return None; }
+    let key_size = bytes.len() * 2 / 9;
+    if !(MIN_KEY_SIZE..=MAX_KEY_SIZE).contains(&key_size) { return None; }
+    let prime_size = key_size / 2;
+
+    // Helper: split the next `len` bytes off the input
+    let mut rest = bytes;
+    let mut take = |len: usize| -> BigUint {
+        let (field, tail) = rest.split_at(len);
+        rest = tail;
+        BigUint::from_be_bytes(field)
+    };
+
     // 1. Read p
-    let mut p_bytes = [0u8; PRIME_SIZE];
-    read_exact(&mut p_bytes)?;
-    let p = BigUint::from_be_bytes(&p_bytes);
+    let p = take(prime_size);
     if p.is_zero() { return None; } // Invalid modulus
 
     // 2. Read q
-    let mut q_bytes = [0u8; PRIME_SIZE];
-    read_exact(&mut q_bytes)?;
-    let q = BigUint::from_be_bytes(&q_bytes);
+    let q = take(prime_size);
     if q.is_zero() { return None; } // Invalid modulus
 
-    // 3. Read n (skip but read to advance stream)
-    let mut n_bytes = [0u8; KEY_SIZE];
-    read_exact(&mut n_bytes)?;
-
-    // 4. Read d (skip)
-    let mut _d_bytes = [0u8; KEY_SIZE];
-    read_exact(&mut _d_bytes)?;
+    // 3. Read n
+    let n = take(key_size);
+    if n.is_zero() { return None; } // Invalid modulus
 
-    // 5. Read dP
-    let mut dp_bytes = [0u8; PRIME_SIZE];
-    read_exact(&mut dp_bytes)?;
-    let dp = BigUint::from_be_bytes(&dp_bytes);
+    // 4. Read d
+    let d = take(key_size);
 
-    // 6. Read dQ
-    let mut dq_bytes = [0u8; PRIME_SIZE];
-    read_exact(&mut dq_bytes)?;
-    let dq = BigUint::from_be_bytes(&dq_bytes);
+    // 5-7. Read dP, dQ, qInv
+    let dp = take(prime_size);
+    let dq = take(prime_size);
+    let qinv = take(prime_size);
 
-    // 7. Read qInv
-    let mut qinv_bytes = [0u8; PRIME_SIZE];
-    read_exact(&mut qinv_bytes)?;
-    let qinv = BigUint::from_be_bytes(&qinv_bytes);
-
-    // 8. Read Ciphertext
-    let mut c_bytes = [0u8; KEY_SIZE];
-    read_exact(&mut c_bytes)?;
-    let c = BigUint::from_be_bytes(&c_bytes);
+    Some(PrivateKey { key_size, p, q, n, d, dp, dq, qinv })
+}
 
-    // ==========================================
-    // 3. CRT Decryption
-    // ==========================================
+// c^d mod n via the CRT exponents and Garner's recombination.
+fn crt_exp(key: &PrivateKey, c: &BigUint) -> BigUint {
+    let PrivateKey { p, q, dp, dq, qinv, .. } = key;
 
     // m1 = c^dP mod p
-    // Reduce c mod p first because c is 2048 bits and p is 1024
-    let c_mod_p = c.rem(&p);
-    let m1 = c_mod_p.modpow(&dp, &p);
+    // Reduce c mod p first because c is twice as wide as p
+    let c_mod_p = c.rem(p);
+    let m1 = c_mod_p.modpow(dp, p);
 
     // m2 = c^dQ mod q
-    let c_mod_q = c.rem(&q);
-    let m2 = c_mod_q.modpow(&dq, &q);
+    let c_mod_q = c.rem(q);
+    let m2 = c_mod_q.modpow(dq, q);
 
     // h = (m1 - m2) * qInv mod p
     // Safe subtraction: if m1 < m2, compute (m1 + p - m2)
@@ -95,7 +213,7 @@ fn try_decrypt() -> Option<Vec<u8>> {
     // so strictly we need m2 % p for the subtraction logic in mod p arithmetic.
     // However, typically Garner's formula uses values reduced by their respective moduli.
     // Let's act strictly in mod p:
-    let m2_mod_p = m2.rem(&p);
+    let m2_mod_p = m2.rem(p);
     
     let diff = if m1.ge(&m2_mod_p) {
         let mut tmp = m1;
@@ -103,85 +221,41 @@ fn try_decrypt() -> Option<Vec<u8>> {
         tmp
     } else {
         let mut tmp = m1;
-        tmp.add_assign(&p);
+        tmp.add_assign(p);
         tmp.sub_assign(&m2_mod_p);
         tmp
     };
 
     // h = diff * qInv mod p
-    let h = diff.mul_mod(&qinv, &p);
+    let h = diff.mul_mod(qinv, p);
 
     // m = m2 + h * q
-    let h_q = h.mul(&q);
+    let h_q = h.mul(q);
     let mut m = m2;
     m.add_assign(&h_q);
-
-    // EM (Encoded Message)
-    let em = m.to_bytes_be(KEY_SIZE);
-
-    // ==========================================
-    // 4. OAEP Decoding
-    // ==========================================
-
-    // 1. Check first byte is 0x00
-    if em[0] != 0x00 { return None; }
-
-    let masked_seed = &em[1..1 + HASH_LEN];
-    let masked_db = &em[1 + HASH_LEN..];
-
-    // 2. Recover Seed
-    let seed_mask = mgf1(masked_db, HASH_LEN);
-    let seed: Vec<u8> = masked_seed.iter().zip(seed_mask.iter()).map(|(a, b)| a ^ b).collect();
-
-    // 3. Recover DB
-    let db_mask = mgf1(&seed, KEY_SIZE - HASH_LEN - 1);
-    let db: Vec<u8> = masked_db.iter().zip(db_mask.iter()).map(|(a, b)| a ^ b).collect();
-
-    // 4. Verify lHash
-    let l_hash_expected = Sha256::digest(&[]);
-    let l_hash_actual = &db[0..HASH_LEN];
-
-    if l_hash_actual != l_hash_expected.as_slice() { return None; }
-
-    // 5. Find 0x01 separator; verify PS is all zeros
-    let mut separator_idx = usize::MAX;
-    for i in HASH_LEN..db.len() {
-        let b = db[i];
-        if b == 0x01 {
-            separator_idx = i;
-            break;
-        }
-        if b != 0x00 {
-            // PS must be all zeros
-            return None;
-        }
-    }
-
-    if separator_idx == usize::MAX { return None; }
-
-    // 6. Extract Message
-    Some(db[separator_idx + 1..].to_vec())
+    m
 }
 
-fn mgf1(seed: &[u8], len: usize) -> Vec<u8> {
-    let mut t = Vec::with_capacity(len + HASH_LEN);
-    let mut counter = 0u32;
-    while t.len() < len {
+// XOR MGF1(seed, out.len()) into `out` one hash block at a time, so the mask
+// never needs a buffer of its own.
+fn mgf1_xor(seed: &[u8], out: &mut [u8]) {
+    for (counter, chunk) in out.chunks_mut(HASH_LEN).enumerate() {
         let mut hasher = Sha256::new();
         hasher.update(seed);
-        hasher.update(&counter.to_be_bytes());
-        t.extend_from_slice(&hasher.finalize());
-        counter += 1;
+        hasher.update(&(counter as u32).to_be_bytes());
+        for (o, m) in chunk.iter_mut().zip(hasher.finalize()) {
+            *o ^= m;
+        }
     }
-    t.truncate(len);
-    t
 }
 
 // ==========================================
 // 5. BigUint Implementation
 // ==========================================
 
-const LIMBS: usize = 66; // Safe margin for 2048 bits + operations
+// Products of two values below an 8192-bit modulus take 256 limbs; two more
+// give a safe margin for carries.
+const LIMBS: usize = 2 * MAX_KEY_SIZE / 8 + 2;
 
 #[derive(Debug, Clone, Copy)]
 struct BigUint {
@@ -213,9 +287,9 @@ impl BigUint {
         Self { data }
     }
 
-    fn to_bytes_be(&self, width: usize) -> Vec<u8> {
+    fn to_bytes_be(self, width: usize) -> Vec<u8> {
         let mut res = vec![0u8; width];
-        let mut temp = *self;
+        let mut temp = self;
         for i in (0..width).rev() {
             res[i] = (temp.data[0] & 0xFF) as u8;
             temp.shr_8();
@@ -239,7 +313,7 @@ impl BigUint {
         
         r_val = r_val.rem(modulus); 
         let r_sq = r_val.mul_mod(&r_val, modulus); 
-        let mut x = self.mont_mul(&r_sq, modulus, n_prime, n_limbs);
+        let x = self.mont_mul(&r_sq, modulus, n_prime, n_limbs);
         let mut res = r_val; 
 
         let mut exp_bits = LIMBS * 64; 
@@ -410,6 +484,16 @@ impl BigUint {
         }
     }
 
+    // Number of significant bits (0 for zero).
+    fn bits(&self) -> usize {
+        for i in (0..LIMBS).rev() {
+            if self.data[i] != 0 {
+                return i * 64 + 64 - self.data[i].leading_zeros() as usize;
+            }
+        }
+        0
+    }
+
     fn shl_1(&mut self) {
         let mut carry = 0;
         for i in 0..LIMBS {
@@ -465,7 +549,7 @@ impl Sha256 {
         }
     }
 
-    fn finalize(mut self) -> Vec<u8> {
+    fn finalize(mut self) -> [u8; HASH_LEN] {
         let i = self.datalen;
         self.data[i] = 0x80;
         self.datalen += 1;
@@ -478,12 +562,12 @@ impl Sha256 {
         let bits = self.bitlen.to_be_bytes();
         for (idx, &b) in bits.iter().enumerate() { self.data[56 + idx] = b; }
         self.transform();
-        let mut out = Vec::with_capacity(32);
-        for s in self.state.iter() { out.extend_from_slice(&s.to_be_bytes()); }
+        let mut out = [0u8; HASH_LEN];
+        for (chunk, s) in out.chunks_mut(4).zip(self.state.iter()) { chunk.copy_from_slice(&s.to_be_bytes()); }
         out
     }
     
-    fn digest(input: &[u8]) -> Vec<u8> {
+    fn digest(input: &[u8]) -> [u8; HASH_LEN] {
         let mut h = Sha256::new();
         h.update(input);
         h.finalize()
@@ -491,8 +575,8 @@ impl Sha256 {
 
     fn transform(&mut self) {
         let mut m = [0u32; 64];
-        for i in 0..16 {
-            m[i] = BigEndian::read_u32(&self.data[i * 4..(i + 1) * 4]);
+        for (word, chunk) in m.iter_mut().zip(self.data.chunks(4)) {
+            *word = BigEndian::read_u32(chunk);
         }
         for i in 16..64 {
             let s0 = m[i - 15].rotate_right(7) ^ m[i - 15].rotate_right(18) ^ (m[i - 15] >> 3);
@@ -526,3 +610,319 @@ impl Sha256 {
         self.state[6] = self.state[6].wrapping_add(g); self.state[7] = self.state[7].wrapping_add(h);
     }
 }
+
+// ==========================================
+// 7. RSASSA-PSS Signing (RFC 8017 8.1.1)
+// ==========================================
+
+fn sign_stdin(args: &[String]) -> io::Result<()> {
+    let usage = || -> ! {
+        eprintln!("usage: code-2 --sign <decrypt-key> [--salt-len N | --pkcs1] < message > signature");
+        std::process::exit(2);
+    };
+    let (key_path, salt_len) = match args {
+        [key] => (key, Some(HASH_LEN)),
+        [key, flag] if flag == "--pkcs1" => (key, None),
+        [key, flag, len] if flag == "--salt-len" => {
+            (key, Some(len.parse().unwrap_or_else(|_| usage())))
+        }
+        _ => usage(),
+    };
+    let key = parse_private_key(&std::fs::read(key_path)?)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed decrypt key"))?;
+
+    // mHash = Hash(M), streaming the message from stdin
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 8192];
+    let mut handle = io::stdin().lock();
+    loop {
+        let read = handle.read(&mut buf)?;
+        if read == 0 { break; }
+        hasher.update(&buf[..read]);
+    }
+    let m_hash = hasher.finalize();
+
+    let em = match salt_len {
+        Some(salt_len) => {
+            // emLen >= hLen + sLen + 2; check before allocating the salt so
+            // a huge --salt-len is an error rather than an allocation failure
+            let em_len = (key.n.bits() - 1).div_ceil(8);
+            if em_len.checked_sub(HASH_LEN + 2).is_none_or(|max_salt| salt_len > max_salt) {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "salt too long for the modulus"));
+            }
+            let mut salt = vec![0u8; salt_len];
+            OsRng.try_fill_bytes(&mut salt).map_err(io::Error::other)?;
+
+            // emBits = modBits - 1, so that EM is always below n
+            pss_encode(&m_hash, &salt, key.n.bits() - 1).expect("salt length checked above")
+        }
+        // Every supported modulus has room for the 62-byte minimum
+        None => pkcs1_sign_encode(&m_hash, key.key_size),
+    };
+
+    // s = EM^d mod n through the same CRT path as decryption
+    let s = crt_exp(&key, &BigUint::from_be_bytes(&em));
+    io::stdout().write_all(&s.to_bytes_be(key.key_size))
+}
+
+// EMSA-PSS-ENCODE with SHA-256 and MGF1-SHA-256; EM is ceil(em_bits / 8) bytes.
+fn pss_encode(m_hash: &[u8], salt: &[u8], em_bits: usize) -> Option<Vec<u8>> {
+    let em_len = em_bits.div_ceil(8);
+    if em_len < HASH_LEN + salt.len() + 2 { return None; }
+
+    // H = Hash(0x00 * 8 || mHash || salt)
+    let mut hasher = Sha256::new();
+    hasher.update(&[0u8; 8]);
+    hasher.update(m_hash);
+    hasher.update(salt);
+    let h = hasher.finalize();
+
+    // DB = PS || 0x01 || salt
+    let mut masked_db = vec![0u8; em_len - salt.len() - HASH_LEN - 2];
+    masked_db.push(0x01);
+    masked_db.extend_from_slice(salt);
+    mgf1_xor(&h, &mut masked_db);
+
+    // Clear the leftmost 8 * emLen - emBits bits
+    masked_db[0] &= 0xFF >> (8 * em_len - em_bits);
+
+    // EM = maskedDB || H || 0xbc
+    let mut em = masked_db;
+    em.extend_from_slice(&h);
+    em.push(0xbc);
+    Some(em)
+}
+
+// ==========================================
+// 8. PKCS#1 v1.5 (RFC 8017 7.2 and 8.2)
+// ==========================================
+
+// DER encoding of DigestInfo { AlgorithmIdentifier { sha256, NULL }, OCTET STRING (32) }
+const SHA256_DIGEST_INFO: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01,
+    0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0x04, 0x20,
+];
+
+// EMSA-PKCS1-v1_5: EM = 0x00 || 0x01 || 0xff... || 0x00 || DigestInfo || H
+fn pkcs1_sign_encode(m_hash: &[u8], em_len: usize) -> Vec<u8> {
+    let t_len = SHA256_DIGEST_INFO.len() + HASH_LEN;
+    let mut em = vec![0x00, 0x01];
+    em.resize(em_len - t_len - 1, 0xFF);
+    em.push(0x00);
+    em.extend_from_slice(&SHA256_DIGEST_INFO);
+    em.extend_from_slice(m_hash);
+    em
+}
+
+// RSAES-PKCS1-v1_5 decoding with implicit rejection
+// (draft-irtf-cfrg-rsa-guidance, as implemented by OpenSSL 3.2+).
+// A malformed EM yields a synthetic message derived from d and the ciphertext
+// instead of an error, and the padding checks run without data-dependent
+// branches, so neither the output nor the timing is a Bleichenbacher oracle.
+fn pkcs1_decode(key: &PrivateKey, c_bytes: &[u8], em: &[u8]) -> Vec<u8> {
+    let k = em.len();
+
+    // KDK = HMAC-SHA256(SHA256(I2OSP(d, k)), C)
+    let d_hash = Sha256::digest(&key.d.to_bytes_be(k));
+    let kdk = hmac_sha256(&d_hash, &[c_bytes]);
+
+    // Synthetic length: the last 16-bit candidate, masked to the bit width
+    // of k - 10, that is below k - 10
+    let candidate_lengths = implicit_rejection_prf(&kdk, b"length", 256);
+    let synthetic = implicit_rejection_prf(&kdk, b"message", k);
+    let max_sep_offset = k - 2 - 8;
+    let mut len_mask = max_sep_offset;
+    len_mask |= len_mask >> 1;
+    len_mask |= len_mask >> 2;
+    len_mask |= len_mask >> 4;
+    len_mask |= len_mask >> 8;
+    let mut synthetic_len = 0;
+    for pair in candidate_lengths.chunks(2) {
+        let candidate = BigEndian::read_u16(pair) as usize & len_mask;
+        synthetic_len = ct_select(ct_lt(candidate, max_sep_offset), candidate, synthetic_len);
+    }
+
+    // EM = 0x00 || 0x02 || PS (at least 8 non-zero bytes) || 0x00 || M
+    let mut good = ct_is_zero(em[0] as usize) & ct_eq(em[1] as usize, 2);
+    let mut found_zero = 0;
+    let mut zero_index = 0;
+    for (i, &b) in em.iter().enumerate().skip(2) {
+        let is_zero = ct_is_zero(b as usize);
+        zero_index = ct_select(!found_zero & is_zero, i, zero_index);
+        found_zero |= is_zero;
+    }
+    good &= ct_ge(zero_index, 2 + 8);
+
+    let msg_index = ct_select(good, zero_index + 1, k - synthetic_len);
+    let source: Vec<u8> = em
+        .iter()
+        .zip(synthetic.iter())
+        .map(|(&a, &b)| (a & good as u8) | (b & !good as u8))
+        .collect();
+    source[msg_index..].to_vec()
+}
+
+// IRPRF(KDK, label, length): HMAC(KDK, I2OSP(i, 2) || label || I2OSP(bits, 2)) blocks
+fn implicit_rejection_prf(kdk: &[u8], label: &[u8], len: usize) -> Vec<u8> {
+    let bits = ((len * 8) as u16).to_be_bytes();
+    let mut out = Vec::with_capacity(len + HASH_LEN);
+    let mut i = 0u16;
+    while out.len() < len {
+        out.extend_from_slice(&hmac_sha256(kdk, &[&i.to_be_bytes(), label, &bits]));
+        i += 1;
+    }
+    out.truncate(len);
+    out
+}
+
+fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; HASH_LEN] {
+    // Keys longer than the 64-byte block are hashed first (RFC 2104)
+    let mut block = [0u8; 64];
+    if key.len() > block.len() {
+        block[..HASH_LEN].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(&block.map(|b| b ^ 0x36));
+    for part in parts {
+        inner.update(part);
+    }
+    let mut outer = Sha256::new();
+    outer.update(&block.map(|b| b ^ 0x5c));
+    outer.update(&inner.finalize());
+    outer.finalize()
+}
+
+// ==========================================
+// 9. Constant-Time Helpers
+// ==========================================
+// Masks are all ones for true and all zeros for false. Values compared with
+// ct_lt / ct_ge must stay below 2^63, which holds for every length here.
+// black_box hides that a mask can only be 0 or !0, which would otherwise let
+// the optimiser turn mask arithmetic back into data-dependent branches.
+
+fn ct_is_zero(x: usize) -> usize {
+    black_box(((!x & x.wrapping_sub(1)) as isize >> (usize::BITS - 1)) as usize)
+}
+
+fn ct_eq(a: usize, b: usize) -> usize {
+    ct_is_zero(a ^ b)
+}
+
+fn ct_lt(a: usize, b: usize) -> usize {
+    black_box((a.wrapping_sub(b) as isize >> (usize::BITS - 1)) as usize)
+}
+
+fn ct_ge(a: usize, b: usize) -> usize {
+    !ct_lt(a, b)
+}
+
+fn ct_select(mask: usize, a: usize, b: usize) -> usize {
+    (a & mask) | (b & !mask)
+}
+
+// ==========================================
+// 10. Timing Harness
+// ==========================================
+
+// |t| above this is strong evidence of a timing difference (dudect).
+const T_THRESHOLD: f64 = 4.5;
+// Measurements are taken in batches holding each class equally often.
+const BATCH: usize = 1000;
+// A difference must show up in every attempt to count; one noisy run on a
+// busy machine is not enough.
+const ATTEMPTS: usize = 3;
+
+// `--timing <decrypt-key> <ciphertext-a> <ciphertext-b> [samples]` times
+// oaep_check on the two ciphertexts in random order and compares the two
+// distributions with Welch's t-test. Both ciphertexts are decrypted up
+// front, so only the padding checks are measured; copying out the message
+// happens after the (public) success or failure and is left out.
+fn timing_harness(args: &[String]) -> io::Result<()> {
+    let usage = || -> ! {
+        eprintln!("usage: code-2 --timing <decrypt-key> <ciphertext-a> <ciphertext-b> [samples >= {BATCH}]");
+        std::process::exit(2);
+    };
+    let (key_path, a_path, b_path, samples) = match args {
+        [key, a, b] => (key, a, b, 200_000),
+        [key, a, b, n] => match n.parse() {
+            Ok(n) if n >= BATCH => (key, a, b, n),
+            _ => usage(),
+        },
+        _ => usage(),
+    };
+    let key = parse_private_key(&std::fs::read(key_path)?)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed decrypt key"))?;
+
+    let mut ems = Vec::new();
+    for path in [a_path, b_path] {
+        let c = std::fs::read(path)?;
+        if c.len() != key.key_size {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "ciphertext length does not match the key"));
+        }
+        ems.push(crt_exp(&key, &BigUint::from_be_bytes(&c)).to_bytes_be(key.key_size));
+    }
+    let mut db = [0u8; MAX_KEY_SIZE];
+    let db = &mut db[..key.key_size - HASH_LEN - 1];
+
+    // Warm up caches and branch predictors before measuring
+    for em in ems.iter().cycle().take(BATCH) {
+        black_box(oaep_check(black_box(em), black_box(&mut *db)));
+    }
+
+    for attempt in 1..=ATTEMPTS {
+        let mut times = [Vec::with_capacity(samples / 2), Vec::with_capacity(samples / 2)];
+        let mut order: Vec<usize> = (0..BATCH).map(|i| i % 2).collect();
+        for _ in 0..samples / BATCH {
+            shuffle(&mut order);
+            for &class in &order {
+                let em = black_box(&ems[class]);
+                let start = Instant::now();
+                black_box(oaep_check(em, black_box(&mut *db)));
+                times[class].push(start.elapsed().as_nanos() as f64);
+            }
+        }
+
+        // Drop the slowest 10% of each class (interrupts, context switches)
+        // before comparing, as dudect does; a single global cutoff would
+        // crop more of whichever class happens to run slower
+        let [(na, ma, va), (nb, mb, vb)] = times.map(|mut t| {
+            t.sort_by(f64::total_cmp);
+            t.truncate(t.len() * 9 / 10);
+            mean_var(t.into_iter())
+        });
+
+        let t = (ma - mb) / (va / na + vb / nb).sqrt();
+        println!("attempt {attempt}: a: n = {na}, mean = {ma:.1} ns; b: n = {nb}, mean = {mb:.1} ns; t = {t:.2}");
+        if t.abs() < T_THRESHOLD {
+            println!("no timing difference detected");
+            return Ok(());
+        }
+    }
+    println!("timing difference detected");
+    std::process::exit(1);
+}
+
+// Fisher-Yates shuffle driven by the OS generator.
+fn shuffle(items: &mut [usize]) {
+    let mut random = [0u8; 8];
+    for i in (1..items.len()).rev() {
+        OsRng.try_fill_bytes(&mut random).unwrap();
+        let j = (u64::from_le_bytes(random) % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+// Count, mean and sample variance (Welford's algorithm).
+fn mean_var(samples: impl Iterator<Item = f64>) -> (f64, f64, f64) {
+    let (mut n, mut mean, mut m2) = (0.0, 0.0, 0.0);
+    for x in samples {
+        n += 1.0;
+        let delta = x - mean;
+        mean += delta / n;
+        m2 += delta * (x - mean);
+    }
+    (n, mean, m2 / (n - 1.0))
+}